@@ -1,13 +1,12 @@
 use mewo::*;
-use std::{thread, time::Duration};
 
 mod pipe;
 mod player;
 mod term;
 
-use pipe::{game_pipe_border, game_pipe_despawn, game_pipe_move, game_pipe_spawn_loop, spawn_pipe};
+use pipe::{game_pipe_border, game_pipe_despawn, game_pipe_move, game_pipe_spawn_loop};
 use player::{game_player_border, game_player_gravity, game_player_jump};
-use term::{term_init, term_input, term_render};
+use term::TermPlugin;
 
 //  TODO FIX: Game Scale Factor
 
@@ -65,6 +64,9 @@ const MIN_X: f32 = 90.0;
 const MIN_Y: f32 = 28.0;
 const PIPE_THICKNESS: i32 = 5;
 const PIPE_GAP: i32 = 10;
+const FIXED_HZ: f64 = 60.0;
+//  About 1300ms.
+const PIPE_SPAWN_STEPS: usize = 78;
 
 fn game_init(g: &Galaxy) {
     if let Some(tc) = g.get_resource::<TermContext, _>(TermContext::single_resource()) {
@@ -81,8 +83,6 @@ fn game_init(g: &Galaxy) {
             .insert(Player(bounds.0 / 2.0, bounds.1 / 2.0))
             .get_entity();
         g.insert_resource(PlayerEntity::single_resource(), PlayerEntity(player));
-
-        spawn_pipe(g).unwrap();
     }
 }
 
@@ -94,42 +94,78 @@ fn game_quit(g: &Galaxy) {
     }
 }
 
-fn main() {
-    let galaxy = Arc::new(RwLock::new(Galaxy::new()));
-
-    {
-        let mut galaxy = galaxy.write();
+struct GamePlugin;
 
-        term_init(&galaxy);
-        game_init(&galaxy);
+impl Plugin for GamePlugin {
+    fn dependencies() -> Vec<&'static str> {
+        vec![TermPlugin::name()]
+    }
 
-        galaxy.update();
+    fn build(&self, stages: StagesBuilder) -> StagesBuilder {
+        stages
+            .insert(Stage::Startup, System::new(game_init).after("term"))
+            .insert(
+                Stage::Update,
+                System::new(game_quit).run_if(Condition::on_event::<TermKeyEvent>()),
+            )
+            .insert(
+                Stage::Update,
+                System::new(game_player_jump)
+                    .label("physics")
+                    .run_if(Condition::on_event::<TermKeyEvent>()),
+            )
+            .insert(
+                Stage::Update,
+                System::new(game_player_border).after("physics"),
+            )
+            .insert(
+                Stage::Update,
+                System::new(game_pipe_despawn).after("physics"),
+            )
+            .insert(
+                Stage::Update,
+                System::new(game_pipe_border)
+                    .after("physics")
+                    .access::<(&Player, &Pipe)>()
+                    .resource::<PlayerEntity>(),
+            )
     }
+}
 
-    let systems_join = run_spawn(
-        Arc::clone(&galaxy),
-        &[
-            game_quit,
-            term_input,
-            term_render,
-            game_player_jump,
-            game_player_border,
-            game_player_gravity,
-            game_pipe_move,
-            game_pipe_despawn,
-            game_pipe_border,
-        ],
-        |_| (),
-        |_| (),
-    );
+fn main() {
+    let galaxy = Arc::new(RwLock::new(Galaxy::new()));
 
-    let systems_1000ms_join = run_spawn(
+    //  Runs at `FIXED_HZ` no matter how fast frames are.
+    let fixed = ScheduleBuilder::new()
+        .insert(System::new(game_player_gravity))
+        .insert(System::new(game_pipe_move))
+        .insert(
+            System::new(game_pipe_spawn_loop)
+                .run_if(Condition::every_n_frames(PIPE_SPAWN_STEPS))
+                .resource::<GameBounds>(),
+        )
+        .build()
+        .unwrap();
+
+    let stages = StagesBuilder::new()
+        .add_plugin(GamePlugin)
+        .add_plugin(TermPlugin)
+        .build()
+        .unwrap();
+
+    let exit = run_spawn_fixed(
         Arc::clone(&galaxy),
-        &[game_pipe_spawn_loop],
-        |_| thread::sleep(Duration::from_millis(1300)),
+        fixed,
+        stages,
+        FixedTimestep::from_hz(FIXED_HZ),
+        |_| (),
         |_| (),
-    );
+    )
+    .join()
+    .unwrap();
 
-    systems_join.join().unwrap();
-    systems_1000ms_join.join().unwrap();
+    if let Exit::Failure { code, message } = exit {
+        println!("Game Over: {}", message);
+        std::process::exit(code);
+    }
 }
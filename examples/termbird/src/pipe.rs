@@ -26,18 +26,15 @@ pub fn game_pipe_spawn_loop(g: &Galaxy) {
     spawn_pipe(g).unwrap();
 }
 
-pub fn game_pipe_move(g: &Galaxy) {
-    for pipe in g.query::<&mut Pipe>().iter() {
+pub fn game_pipe_move(pipes: Query<&mut Pipe>) {
+    for pipe in pipes.iter() {
         pipe.0 .0 += 0.5;
         pipe.1 .0 += 0.5;
     }
 }
 
-pub fn game_pipe_despawn(g: &Galaxy) {
-    let bounds = g
-        .get_resource::<GameBounds, _>(GameBounds::single_resource())
-        .unwrap();
-    for (e, pipe) in g.query::<&Pipe>().eiter() {
+pub fn game_pipe_despawn(g: &Galaxy, pipes: Query<&Pipe>, bounds: Res<GameBounds>) {
+    for (e, pipe) in pipes.eiter() {
         if pipe.0 .0 >= bounds.0 {
             g.remove_entity(e);
         }
@@ -63,7 +60,7 @@ pub fn game_pipe_border(g: &Galaxy) {
                 && player_pos.1 <= pipe.1 .1 + pipe.1 .3 as f32)
         {
             g.remove_entity(player.get_entity());
-            g.set_exit();
+            g.set_exit_with(Exit::failure(1, "Hit a pipe!"));
         }
     }
     // }
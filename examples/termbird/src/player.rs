@@ -1,29 +1,26 @@
 use super::*;
 
-pub fn game_player_gravity(g: &Galaxy) {
-    for player in g.query::<&mut Player>().iter() {
+pub fn game_player_gravity(players: Query<&mut Player>) {
+    for player in players.iter() {
         player.1 += 0.22;
     }
 }
 
-pub fn game_player_jump(g: &Galaxy) {
-    for ev in g.get_events::<TermKeyEvent>() {
+pub fn game_player_jump(players: Query<&mut Player>, keys: Events<TermKeyEvent>) {
+    for ev in keys.iter() {
         if ev.key == ' ' as u16 {
-            for player in g.query::<&mut Player>().iter() {
+            for player in players.iter() {
                 player.1 -= 6.0;
             }
         }
     }
 }
 
-pub fn game_player_border(g: &Galaxy) {
-    let bounds = g
-        .get_resource::<GameBounds, _>(GameBounds::single_resource())
-        .unwrap();
-    for (e, player) in g.query::<&Player>().eiter() {
+pub fn game_player_border(g: &Galaxy, players: Query<&Player>, bounds: Res<GameBounds>) {
+    for (e, player) in players.eiter() {
         if player.1 >= bounds.1 || player.1 <= 0f32 {
             g.remove_entity(e);
-            g.set_exit();
+            g.set_exit_with(Exit::failure(1, "Fell out of bounds!"));
         }
     }
 }
@@ -1,36 +1,67 @@
 use super::*;
 use termbox_sys::*;
 
-impl TermContext {}
+//  Terminal setup, input, rendering and teardown.
+pub struct TermPlugin;
 
-pub fn term_init(g: &Galaxy) {
+impl Plugin for TermPlugin {
+    fn build(&self, stages: StagesBuilder) -> StagesBuilder {
+        stages
+            .register_event::<TermKeyEvent>()
+            .insert(Stage::Startup, System::new(term_init).label("term"))
+            //  Key events are visible to `Update` in the same frame.
+            .insert(
+                Stage::PreUpdate,
+                System::new(term_input)
+                    .run_if(Condition::resource_exists::<TermContext, _>(
+                        TermContext::single_resource(),
+                    ))
+                    .resource_mut::<TermContext>(),
+            )
+            .update_after(Stage::PreUpdate)
+            .insert(
+                Stage::Last,
+                System::new(term_render)
+                    .access::<(&Player, &Pipe)>()
+                    .resource_mut::<TermContext>(),
+            )
+            .insert(
+                Stage::Shutdown,
+                System::new(term_shutdown).resource_mut::<TermContext>(),
+            )
+    }
+}
+
+fn term_init(g: &Galaxy) {
     g.insert_resource(TermContext::single_resource(), TermContext::new());
 }
 
-pub fn term_input(g: &Galaxy) {
-    if g.get_mut_resource::<TermContext, _>(TermContext::single_resource())
-        .is_some()
-    {
-        let mut ev = std::mem::MaybeUninit::uninit();
-        let ev = unsafe {
-            //  60fps right?
-            match tb_peek_event(ev.as_mut_ptr(), 16) {
-                0 => return,
-                -1 => panic!(),
-                _ => ev.assume_init(),
-            }
-        };
-        if ev.etype == TB_EVENT_KEY {
-            g.insert_event(TermKeyEvent {
-                key: ev.key,
-                unicode: ev.ch,
-            });
+//  Restores the terminal before anything is printed.
+fn term_shutdown(g: &Galaxy) {
+    g.remove_resource::<TermContext, _>(TermContext::single_resource());
+}
+
+//  Only runs while `TermContext` exists.
+fn term_input(g: &Galaxy) {
+    let mut ev = std::mem::MaybeUninit::uninit();
+    let ev = unsafe {
+        //  60fps right?
+        match tb_peek_event(ev.as_mut_ptr(), 16) {
+            0 => return,
+            -1 => panic!(),
+            _ => ev.assume_init(),
         }
+    };
+    if ev.etype == TB_EVENT_KEY {
+        g.insert_event(TermKeyEvent {
+            key: ev.key,
+            unicode: ev.ch,
+        });
     }
 }
 
 //  Render Players and Pipes
-pub fn term_render(g: &Galaxy) {
+fn term_render(g: &Galaxy) {
     unsafe { tb_clear() };
     for player in g.query::<&Player>().iter() {
         unsafe {
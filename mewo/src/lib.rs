@@ -50,7 +50,16 @@
 //! `UniqueComponent` is for components that either don't implement copy or shouldn't be copied too
 //! often.
 //!
-//! ## Defining Resources 
+//! A `CheapComponent` is double buffered, so writes only show after the next update.
+//! Large components or ones written to rarely can opt out and be stored directly instead.
+//!
+//! ```rust,ignore
+//! #[derive(Clone, Copy, CheapComponent)]
+//! #[mewo(storage = "direct")]
+//! struct Tile([u8; 256]);
+//! ```
+//!
+//! ## Defining Resources
 //!
 //! Resources are pieces of data that just exist and can be accessed with some value.
 //!
@@ -80,6 +89,208 @@
 //! }
 //! ```
 //!
+//! Closures work too, and state that only one system needs can live in a `Local`.
+//!
+//! ```rust,ignore
+//! fn spawn_timer(galaxy: &Galaxy, mut frames: Local<usize>) {
+//!     *frames += 1;
+//! }
+//!
+//! run_single(galaxy, vec![Box::new(my_system), Box::new(with_local(spawn_timer))]);
+//! ```
+//!
+//! ## Parallel Systems
+//!
+//! Systems can declare which components and resources they access.
+//! Systems that don't conflict are run in parallel.
+//!
+//! ```rust,ignore
+//! let schedule = ScheduleBuilder::new()
+//!     .insert(System::new(player_move).access::<(&mut Player, &Velocity)>())
+//!     .insert(System::new(enemy_move).access::<(&mut Enemy, &Velocity)>())
+//!     .insert(System::new(render).access::<(&Player, &Enemy)>().resource_mut::<Window>())
+//!     .build()
+//!     .unwrap();
+//!
+//! run_spawn_parallel(Arc::clone(&galaxy), schedule, |_| (), |_| ()).join().unwrap();
+//! ```
+//!
+//! Systems that conflict still run in the order that they were inserted.
+//! A system that takes `&Galaxy` could read anything, so it never runs alongside a system that
+//! writes.
+//! Here, `player_move` and `enemy_move` both take `&Galaxy`, so they take turns.
+//! Use [System Parameters](#system-parameters) instead to let them run together.
+//! Writes through `&Galaxy` aren't checked, so declare them!
+//!
+//! ## System Parameters
+//!
+//! Instead of `&Galaxy`, systems can take parameters that fetch what they need.
+//! `System::new` declares the accesses of each parameter for you.
+//! It panics if two parameters conflict, such as `Query<&mut Pipe>` and `Query<&Pipe>`, since the
+//! system would wait on itself.
+//!
+//! ```rust,ignore
+//! fn pipe_move(
+//!     pipes: Query<(&Player, &mut Pipe)>,
+//!     bounds: Res<GameBounds>,
+//!     keys: Events<TermKeyEvent>,
+//!     mut moves: Local<usize>,
+//! ) {
+//!     for (player, pipe) in pipes.iter() {
+//!         todo!();
+//!     }
+//! }
+//!
+//! ScheduleBuilder::new().insert(System::new(pipe_move));
+//! ```
+//!
+//! `ResMut<R>` writes a resource, `Option<Res<R>>` handles resources that may not exist, and
+//! `&Galaxy` can be mixed in for everything else.
+//!
+//! ## Ordering Systems
+//!
+//! Systems can be labeled and ordered relative to labels.
+//!
+//! ```rust,ignore
+//! let schedule = ScheduleBuilder::new()
+//!     .insert(System::new(render).after("physics"))
+//!     .insert(System::new(gravity).label("physics").after("input"))
+//!     .insert(System::new(input).label("input"))
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! `build` fails if a label doesn't exist or if the constraints form a cycle.
+//!
+//! ## Run Conditions
+//!
+//! Systems and labels can be given conditions that decide whether they run each frame.
+//!
+//! ```rust,ignore
+//! let schedule = ScheduleBuilder::new()
+//!     .insert(System::new(jump).run_if(Condition::on_event::<KeyEvent>()))
+//!     .insert(System::new(spawn_enemy).label("spawning"))
+//!     .set_run_if(
+//!         "spawning",
+//!         Condition::every_n_frames(60)
+//!             .and(!Condition::resource_exists::<GameOver, _>(GameOver::single_resource())),
+//!     )
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! Conditions are evaluated once per frame before any system runs.
+//!
+//! ## Stages
+//!
+//! Systems can be split into stages.
+//! `Startup` runs once, then `PreUpdate`, `Update`, `PostUpdate` and `Last` run every frame.
+//! The galaxy is updated after `Startup`, at the end of each frame, and after any stage passed
+//! to `update_after`.
+//!
+//! ```rust,ignore
+//! let stages = StagesBuilder::new()
+//!     .insert(Stage::Startup, System::new(spawn_player))
+//!     .insert(Stage::PreUpdate, System::new(read_input))
+//!     .insert(Stage::Update, System::new(player_move))
+//!     .insert(Stage::Last, System::new(render))
+//!     //  Events from `read_input` are visible to `player_move` in the same frame.
+//!     .update_after(Stage::PreUpdate)
+//!     .build()
+//!     .unwrap();
+//!
+//! run_spawn_stages(Arc::clone(&galaxy), stages, |_| (), |_| ()).join().unwrap();
+//! ```
+//!
+//! Ordering with `before` and `after` only applies within a stage.
+//!
+//! ## Exclusive Systems
+//!
+//! Exclusive systems borrow the galaxy mutably.
+//! They run at the end of their stage once every other system has completed, and before the
+//! stage's update.
+//!
+//! ```rust,ignore
+//! StagesBuilder::new().insert_exclusive(Stage::PostUpdate, |galaxy: &mut Galaxy| {
+//!     load_level(galaxy);
+//! });
+//! ```
+//!
+//! ## Plugins
+//!
+//! Plugins bundle resources, events, components and systems into one `add_plugin` call.
+//! `build` on the `StagesBuilder` fails if a dependency is missing or a plugin is added twice.
+//!
+//! ```rust,ignore
+//! struct GamePlugin;
+//!
+//! impl Plugin for GamePlugin {
+//!     fn dependencies() -> Vec<&'static str> {
+//!         vec![TermPlugin::name()]
+//!     }
+//!
+//!     fn build(&self, stages: StagesBuilder) -> StagesBuilder {
+//!         stages
+//!             .insert_resource(Score::single_resource(), Score(0))
+//!             .register_event::<ScoreEvent>()
+//!             .insert(Stage::Update, System::new(score_update))
+//!     }
+//! }
+//!
+//! let stages = StagesBuilder::new()
+//!     .add_plugin(TermPlugin)
+//!     .add_plugin(GamePlugin)
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! ## Fixed Timesteps
+//!
+//! `run_fixed` and `run_spawn_fixed` run one schedule at a fixed rate and stages once per
+//! frame.
+//!
+//! ```rust,ignore
+//! run_spawn_fixed(
+//!     Arc::clone(&galaxy),
+//!     physics_schedule,
+//!     render_stages,
+//!     FixedTimestep::from_hz(60.0).max_steps(4),
+//!     |_| (),
+//!     |_| (),
+//! )
+//! .join()
+//! .unwrap();
+//!
+//! fn render(g: &Galaxy) {
+//!     let alpha = g
+//!         .get_resource::<FixedTime, _>(FixedTime::single_resource())
+//!         .unwrap()
+//!         .alpha();
+//!     //  Blend between the last two physics steps with `alpha`.
+//! }
+//! ```
+//!
+//! ## Time
+//!
+//! Every runner keeps a `Time` resource ticked after each update.
+//! Scaled time follows `set_scale` and stops on `pause`, while real time always moves.
+//! Insert your own `Time` before running to swap the clock, for example, with a `ManualClock` in tests.
+//!
+//! ```rust,ignore
+//! fn move_player(g: &Galaxy) {
+//!     let dt = g
+//!         .get_resource::<Time, _>(Time::single_resource())
+//!         .unwrap()
+//!         .delta()
+//!         .as_secs_f32();
+//!     //  Move by `speed * dt`.
+//! }
+//!
+//! let clock = ManualClock::new();
+//! galaxy.insert_resource(Time::single_resource(), Time::with_clock(clock.clone()));
+//! clock.advance(Duration::from_millis(16));
+//! ```
+//!
 //! ## Spawning an Entity
 //!
 //! ```rust,ignore
@@ -107,6 +318,118 @@
 //! }
 //! ```
 //!
+//! Queries and filters take tuples of up to 12 elements, which can be nested to go further.
+//!
+//! ```rust,ignore
+//! for (player, (pos, vel)) in g.query::<(&Player, (&mut Position, &Velocity))>().with::<(Alive, Visible)>().iter() {
+//!     //  ...
+//! }
+//! ```
+//!
+//! Large queries can be split into batches that run on the thread pool.
+//!
+//! ```rust,ignore
+//! particles.par_for_each(256, |(pos, vel)| {
+//!     pos.0 += vel.0;
+//! });
+//! ```
+//!
+//! `Entity` and `Has<C>` can go anywhere in a query.
+//! `Has` derefs to a `bool` and never locks `C`.
+//!
+//! ```rust,ignore
+//! for (entity, player, frozen) in g.query::<(Entity, &mut Player, Has<Frozen>)>().iter() {
+//!     //  ...
+//! }
+//! ```
+//!
+//! For entities with at least one of some components, use `or` or `AnyOf`.
+//!
+//! ```rust,ignore
+//! for player in g.query::<&Player>().or::<(Walking, Running)>().iter() {
+//!     //  ...
+//! }
+//!
+//! for AnyOf((sprite, text)) in g.query::<AnyOf<(&Sprite, &Text)>>().iter() {
+//!     //  `sprite` and `text` are `Option`s, but at least one is `Some`.
+//! }
+//! ```
+//!
+//! To only see what changed since a system last ran, use `changed` or `added`.
+//! Any `&mut` access counts as a change.
+//! Outside of a `Query` parameter, these are relative to the last `galaxy.update()`.
+//!
+//! ```rust,ignore
+//! fn redraw(sprites: Query<(&Sprite, &Position)>) {
+//!     for (sprite, pos) in sprites.changed::<Position>().iter() {
+//!         //  ...
+//!     }
+//! }
+//! ```
+//!
+//! Writes to a `CheapComponent` don't show until the next update, so `&C` reads last frame's value.
+//! To be explicit about this, use `Prev<&C>`, and use `Live<&C>` to see this frame's writes.
+//! `Live` locks the same way `&mut C` does.
+//!
+//! ```rust,ignore
+//! for (prev, live) in g.query::<(Prev<&Position>, Live<&Position>)>().iter() {
+//!     let velocity = live.0 - prev.0;
+//! }
+//! ```
+//!
+//! To compare matches against each other, like for collisions, use `iter_combinations`.
+//! Each unordered pair (or triple, etc) shows up once.
+//! It can only read, so use `for_each_combination` for `&mut`.
+//!
+//! ```rust,ignore
+//! for [(a, a_pos), (b, b_pos)] in colliders.iter_combinations::<2>() {
+//!     //  ...
+//! }
+//!
+//! bodies.for_each_combination(|[(a_pos, a_vel), (b_pos, b_vel)]| {
+//!     //  ...
+//! });
+//! ```
+//!
+//! `count` and `is_empty` don't touch any components.
+//! When exactly one entity should match, use `single`, or `get_single` to get an error instead of a panic.
+//!
+//! ```rust,ignore
+//! fn follow_player(players: Query<(&Player, &Position)>) {
+//!     let mut player = players.single();
+//!     let (_, position) = player.get();
+//!     //  ...
+//! }
+//! ```
+//!
+//! Components are locked while they're being iterated, so overlapping queries that write to the same component will deadlock.
+//! `try_iter` and `try_get` return an error naming the conflicting components instead.
+//! To find the culprit, `galaxy.set_debug_locks(true)` turns these deadlocks into panics.
+//!
+//! ```rust,ignore
+//! for pos in positions.iter() {
+//!     match velocities.try_iter() {
+//!         Ok(iter) => { /* ... */ }
+//!         Err(e) => eprintln!("{:?}", e.error),
+//!     }
+//! }
+//! ```
+//!
+//! ## Dynamic Queries
+//!
+//! For scripting and tools, queries can also be built from `ComponentTypeId`s at runtime.
+//! Rows come back as raw pointers, one per access, with `get_types` describing each.
+//!
+//! ```rust,ignore
+//! let position = galaxy.get_component_type_id("game::Position").unwrap();
+//! let iter = galaxy.dynamic_query().write(position).iter().unwrap();
+//! let size = iter.get_types()[0].size;
+//! for row in iter {
+//!     let position: *mut u8 = row.datas[0].unwrap() as *mut u8;
+//!     //  ...
+//! }
+//! ```
+//!
 //! ## Getting a Specific Entity
 //!
 //! ```rust,ignore
@@ -123,6 +446,23 @@
 //!
 //! Once again, both inserted and removed components don't show until the next update.
 //!
+//! To read or write a specific entity's components without queueing anything, use a query.
+//! There's no `get_mut`, `&mut` in the query is enough.
+//! `get` returns `None` if the entity doesn't match, filters included.
+//! The components stay locked until the result is dropped.
+//!
+//! ```rust,ignore
+//! fn follow(targets: Query<&Position>, followers: Query<(&Follow, &mut Position)>) {
+//!     //  Copy it out so `Position` isn't still locked below.
+//!     let Some(target) = targets.get(leader).map(|mut target| *target.get()) else {
+//!         return;
+//!     };
+//!     for (follow, pos) in followers.iter() {
+//!         //  ...
+//!     }
+//! }
+//! ```
+//!
 //! ## Spawning a Resource
 //!
 //! ```rust,ignore
@@ -174,10 +514,46 @@
 //!
 //! Similar to components, inserted events don't appear until the next update.
 //!
+//! ## Pausing
+//!
+//! ```rust,ignore
+//! let control = RunControl::new();
+//! let stages = StagesBuilder::new()
+//!     .control(control.clone())
+//!     .insert(Stage::Update, System::new(debug_overlay).run_while_paused())
+//!     .build()
+//!     .unwrap();
+//!
+//! control.pause();
+//! //  Run 3 frames, then pause again.
+//! control.step(3);
+//! //  Or run until a condition holds.
+//! control.run_until(Condition::resource_exists::<Boss, _>(Boss::single_resource()));
+//! control.resume();
+//! ```
+//!
+//! `RunControl` is also a single resource, so systems can pause the runner too.
+//! While paused, only systems marked with `run_while_paused` run and `Time` stands still.
+//!
 //! ## Game Over
 //!
 //! ```rust,ignore
 //! galaxy.set_exit();
+//! //  Or, with a reason.
+//! galaxy.set_exit_with(Exit::failure(1, "Player fell"));
+//! ```
+//!
+//! Runners return the `Exit`, and only the first one is kept.
+//! With stages, `Shutdown` runs once after exiting so resources can be torn down in order.
+//!
+//! ```rust,ignore
+//! let stages = StagesBuilder::new()
+//!     .insert(Stage::Shutdown, System::new(close_window))
+//!     .build()
+//!     .unwrap();
+//!
+//! let exit = run_spawn_stages(Arc::clone(&galaxy), stages, |_| (), |_| ()).join().unwrap();
+//! std::process::exit(exit.get_code());
 //! ```
 //!
 
@@ -68,6 +68,17 @@ impl DVec {
         Some(())
     }
 
+    //  Drop the value at `idx` and copy `inplace` over it.
+    pub unsafe fn replace(&mut self, idx: usize, inplace: *const u8) -> Option<()> {
+        if idx >= self.len {
+            None?
+        }
+        let val = self.get(idx)?;
+        self.drop.call(val);
+        std::ptr::copy_nonoverlapping(inplace, val as *mut u8, self.data_size);
+        Some(())
+    }
+
     pub fn take_swap_remove(&mut self, idx: usize) -> Option<()> {
         if self.data_size != 0 {
             for b in (0..self.data_size).rev() {
@@ -1,5 +1,6 @@
 mod drop;
 mod dvec;
+mod pool;
 mod preserve;
 mod sparse;
 mod threadlocal;
@@ -8,6 +9,7 @@ mod typeentry;
 
 pub use drop::{CloneFunction, DropFunction, ValueDrop, ValueDuplicate};
 pub use dvec::DVec;
+pub use pool::ThreadPool;
 pub use preserve::{Preserve, PreserveInstance};
 pub use sparse::SparseSet;
 pub use threadlocal::{ThreadLocal, ThreadLocalGuard};
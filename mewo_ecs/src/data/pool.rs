@@ -0,0 +1,237 @@
+use parking_lot::{Condvar, Mutex};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Sender},
+        Arc, OnceLock,
+    },
+    thread,
+};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+static GLOBAL_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+pub struct ThreadPool {
+    sender: Mutex<Option<Sender<Job>>>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    pub fn new(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    //  The guard is dropped before running the job.
+                    let job = receiver.lock().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                })
+            })
+            .collect();
+        ThreadPool {
+            sender: Mutex::new(Some(sender)),
+            workers,
+        }
+    }
+
+    //  Like `std::thread::scope`, jobs may borrow from the environment since this function does
+    //  not return until every job spawned in `f` has completed.
+    //  Panics from jobs are resumed here once everything has finished.
+    pub fn scope<'env, F, R>(&self, f: F) -> R
+    where
+        F: for<'scope> FnOnce(&'scope PoolScope<'scope, 'env>) -> R,
+    {
+        let scope = PoolScope {
+            pool: self,
+            state: Arc::new(ScopeState {
+                jobs: Mutex::new(VecDeque::new()),
+                pending: Mutex::new(0),
+                done: Condvar::new(),
+                panic: Mutex::new(None),
+            }),
+            scope: PhantomData,
+            env: PhantomData,
+        };
+        let ret = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        scope.wait();
+        if let Some(payload) = scope.state.panic.lock().take() {
+            panic::resume_unwind(payload)
+        }
+        match ret {
+            Ok(ret) => ret,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    pub fn get_global_pool() -> &'static ThreadPool {
+        GLOBAL_POOL.get_or_init(|| {
+            ThreadPool::new(
+                thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1),
+            )
+        })
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        //  Hanging up makes every worker return.
+        self.sender.lock().take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+//  The pool is only sent a ticket to run one of `jobs`.
+//  That way, a thread waiting on its scope only ever runs that scope's jobs.
+//  Running another scope's job could deadlock if it needs a lock that the waiting thread holds.
+struct ScopeState {
+    jobs: Mutex<VecDeque<Job>>,
+    pending: Mutex<usize>,
+    done: Condvar,
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+}
+
+impl ScopeState {
+    fn run_one(&self) -> bool {
+        //  The guard is dropped before running the job.
+        let job = self.jobs.lock().pop_front();
+        if let Some(job) = job {
+            job();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub struct PoolScope<'scope, 'env: 'scope> {
+    pool: &'scope ThreadPool,
+    state: Arc<ScopeState>,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope, 'env> PoolScope<'scope, 'env> {
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'scope,
+    {
+        *self.state.pending.lock() += 1;
+        let state = Arc::clone(&self.state);
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(f)) {
+                state.panic.lock().get_or_insert(payload);
+            }
+            let mut pending = state.pending.lock();
+            *pending -= 1;
+            if *pending == 0 {
+                state.done.notify_all();
+            }
+        });
+        //  Safety: `ThreadPool::scope` waits on every job before 'scope ends.
+        let job: Job = unsafe { std::mem::transmute(job) };
+        self.state.jobs.lock().push_back(job);
+        //  The job may already be taken by the time the ticket runs.
+        let state = Arc::clone(&self.state);
+        self.pool
+            .sender
+            .lock()
+            .as_ref()
+            .unwrap()
+            .send(Box::new(move || {
+                state.run_one();
+            }))
+            .unwrap();
+    }
+
+    //  Jobs left once the queue is empty are running elsewhere, so just wait for them.
+    fn wait(&self) {
+        loop {
+            if self.state.run_one() {
+                continue;
+            }
+            let mut pending = self.state.pending.lock();
+            if *pending == 0 {
+                return;
+            }
+            self.state.done.wait(&mut pending);
+        }
+    }
+}
+
+#[test]
+fn test_pool_scope() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let pool = ThreadPool::new(2);
+    let count = AtomicUsize::new(0);
+    pool.scope(|s| {
+        for _ in 0..16 {
+            s.spawn(|| {
+                //  Nested scopes shouldn't deadlock a small pool.
+                pool.scope(|s| {
+                    s.spawn(|| {
+                        count.fetch_add(1, Ordering::SeqCst);
+                    });
+                });
+            });
+        }
+    });
+    assert_eq!(count.load(Ordering::SeqCst), 16);
+}
+
+//  A thread waiting on its scope must not pick up another scope's job.
+//  Here, main holds `lock` while waiting, and the other scope's job is queued first.
+#[test]
+fn test_pool_scope_own_jobs() {
+    let pool = ThreadPool::new(1);
+    let lock = Mutex::new(());
+    let ran_on = Mutex::new(None);
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let (queued_tx, queued_rx) = mpsc::channel();
+
+    thread::scope(|ts| {
+        //  Keep the only worker busy.
+        ts.spawn(|| {
+            pool.scope(|s| {
+                s.spawn(move || {
+                    started_tx.send(()).unwrap();
+                    release_rx.recv().unwrap();
+                })
+            })
+        });
+        started_rx.recv().unwrap();
+
+        ts.spawn(|| {
+            pool.scope(|s| {
+                s.spawn(|| {
+                    //  Times out rather than hanging if main picks this up.
+                    let _lock = lock.try_lock_for(std::time::Duration::from_secs(1));
+                    *ran_on.lock() = Some(thread::current().id());
+                });
+                queued_tx.send(()).unwrap();
+            })
+        });
+        queued_rx.recv().unwrap();
+
+        {
+            let _lock = lock.lock();
+            pool.scope(|s| s.spawn(|| {}));
+        }
+        release_tx.send(()).unwrap();
+    });
+    assert_ne!(*ran_on.lock(), Some(thread::current().id()));
+}
@@ -24,19 +24,37 @@ impl ComponentGroupId {
     }
 }
 
+/// How a component is stored.
+/// Only `Copy` components can be double buffered, others are always stored directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentStorage {
+    /// Writes are seen right away.
+    Direct,
+    /// Writes go to a separate copy that replaces the readable one every update.
+    /// The whole copy is made every update, which adds up for large components.
+    DoubleBuffered,
+}
+
 #[derive(Default, Debug)]
 pub struct ComponentTypePlanet {
     components: HashMap<ComponentTypeId, TypeEntry>,
+    storages: HashMap<ComponentTypeId, ComponentStorage>,
 }
 
 impl ComponentTypePlanet {
     pub fn new() -> Self {
         ComponentTypePlanet {
             components: HashMap::new(),
+            storages: HashMap::new(),
         }
     }
 
-    pub fn insert_type(&mut self, id: ComponentTypeId, ty: TypeEntry) -> Result<()> {
+    pub fn insert_type(
+        &mut self,
+        id: ComponentTypeId,
+        ty: TypeEntry,
+        storage: ComponentStorage,
+    ) -> Result<()> {
         if self.components.contains_key(&id) {
             Err(ecs_err!(
                 ErrorType::ComponentTypePlanetInsertType { id, ty: ty.clone() },
@@ -44,6 +62,7 @@ impl ComponentTypePlanet {
             ))?
         }
         self.components.insert(id, ty);
+        self.storages.insert(id, storage);
         Ok(())
     }
 
@@ -52,6 +71,20 @@ impl ComponentTypePlanet {
             .get(&id)
             .ok_or(ecs_err!(ErrorType::ComponentTypePlanetGetType { id }, self))
     }
+
+    pub fn get_storage(&self, id: ComponentTypeId) -> Result<ComponentStorage> {
+        self.storages
+            .get(&id)
+            .copied()
+            .ok_or(ecs_err!(ErrorType::ComponentTypePlanetGetType { id }, self))
+    }
+
+    pub fn get_type_id(&self, name: &str) -> Option<ComponentTypeId> {
+        self.components
+            .iter()
+            .find(|(_, ty)| ty.name == name)
+            .map(|(&id, _)| id)
+    }
 }
 
 //  ComponentGroups are always sorted.
@@ -76,37 +109,7 @@ impl ComponentGroup {
     }
 
     pub fn has(&self, cty: ComponentTypeId) -> bool {
-        self.binary_search_recurse(cty, 0, self.components.len())
-            .is_some()
-    }
-
-    fn binary_search_recurse(
-        &self,
-        find: ComponentTypeId,
-        left: usize,
-        right: usize,
-    ) -> Option<usize> {
-        let middle = (right - left) / 2 + left;
-        let &val = self.components.get(middle)?;
-        if val == find {
-            return Some(middle);
-        }
-        if middle == right {
-            return None;
-        }
-        if val > find {
-            self.binary_search_recurse(
-                find,
-                left,
-                if let Ok(m) = (middle as isize - 1).try_into() {
-                    m
-                } else {
-                    return None;
-                },
-            )
-        } else {
-            self.binary_search_recurse(find, middle + 1, right)
-        }
+        self.components.binary_search(&cty).is_ok()
     }
 }
 
@@ -183,3 +186,18 @@ impl ComponentGroupPlanet {
         &self.groups
     }
 }
+
+#[test]
+fn test_component_group_has() {
+    let mut group = ComponentGroup::new();
+    let mut modify = group.modify();
+    for hash in (0..24).map(|i| i * 2 + 1) {
+        modify.insert(ComponentTypeId::from_hash(hash));
+    }
+    modify.build();
+    for hash in 0..48 {
+        assert_eq!(group.has(ComponentTypeId::from_hash(hash)), hash % 2 == 1);
+    }
+    assert!(!group.has(ComponentTypeId::from_hash(u64::MAX)));
+    assert!(!ComponentGroup::new().has(ComponentTypeId::from_hash(0)));
+}
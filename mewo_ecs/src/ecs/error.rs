@@ -51,6 +51,10 @@ pub enum ErrorType {
         entity: Entity,
         id: ComponentTypeId,
     },
+    StorageBlocReplaceComponent {
+        entity: Entity,
+        id: ComponentTypeId,
+    },
     StorageBlocCopyEntity {
         entity: Entity,
     },
@@ -82,6 +86,25 @@ pub enum ErrorType {
     QueryPlanetUpdate {
         id: ComponentGroupId,
     },
+    QuerySingle {
+        count: usize,
+    },
+    QueryLockConflict {
+        components: Vec<String>,
+    },
+    ScheduleLabelMissing {
+        label: &'static str,
+    },
+    ScheduleCycle {
+        systems: Vec<String>,
+    },
+    PluginDuplicate {
+        plugin: &'static str,
+    },
+    PluginDependencyMissing {
+        plugin: &'static str,
+        dependency: &'static str,
+    },
 }
 
 #[macro_export]
@@ -7,13 +7,15 @@ mod resource;
 mod storage;
 
 pub use component::{
-    ComponentGroup, ComponentGroupId, ComponentGroupPlanet, ComponentTypeId, ComponentTypePlanet,
+    ComponentGroup, ComponentGroupId, ComponentGroupPlanet, ComponentStorage, ComponentTypeId,
+    ComponentTypePlanet,
 };
 pub use entity::{Entity, EntityPlanet};
+pub use error::{ECSError, ErrorType, Result};
 pub use event::{EventId, EventModify, EventPlanet};
 pub use query::{
     QueryAccess, QueryAccessType, QueryFilterType, QueryId, QueryLockType, QueryPlanet,
     StorageAccess,
 };
 pub use resource::{ResourceId, ResourcePlanet, ResourceTypeId};
-pub use storage::{StorageModifyTransform, StoragePlanet, StorageTransform};
+pub use storage::{RowTicks, StorageModifyTransform, StoragePlanet, StorageTransform};
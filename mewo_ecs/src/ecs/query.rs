@@ -26,21 +26,40 @@ pub enum QueryAccessType {
     Write,
     OptionRead,
     OptionWrite,
+    //  Reads what's being written this frame.
+    //  Copycat components only have one lock for this, so it must be a write lock.
+    LiveRead,
 }
 
 impl QueryAccessType {
     pub fn into_lock(self) -> QueryLockType {
         match self {
             QueryAccessType::Read | QueryAccessType::OptionRead => QueryLockType::Read,
-            QueryAccessType::Write | QueryAccessType::OptionWrite => QueryLockType::Write,
+            QueryAccessType::Write | QueryAccessType::OptionWrite | QueryAccessType::LiveRead => {
+                QueryLockType::Write
+            }
         }
     }
+
+    //  Whether this reads from the write buffer of copycat components.
+    pub fn is_live(self) -> bool {
+        !matches!(self, QueryAccessType::Read | QueryAccessType::OptionRead)
+    }
+
+    pub fn is_write(self) -> bool {
+        matches!(self, QueryAccessType::Write | QueryAccessType::OptionWrite)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum QueryFilterType {
     With,
     Without,
+    //  Also `With`, rows are then checked against their ticks while iterating.
+    Changed,
+    Added,
+    //  Groups must have at least one component from each `Or` with the same index.
+    Or(usize),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -187,15 +206,18 @@ fn group_from_access(access: &HashMap<ComponentTypeId, QueryLockType>) -> Compon
 //  1. &C,          4. &mut C
 //  2. Option<&C>   5. Option<&mut C>
 //  3. With<C>      6. Without<C>
+//  7. Changed<C>   8. Added<C>
+//  9. Or<(C0, C1, ...)>    10. Live<&C>
 fn access_filter(
     group: &ComponentGroup,
     accesses: &[(ComponentTypeId, QueryAccessType)],
     filters: &[(ComponentTypeId, QueryFilterType)],
 ) -> Option<HashMap<ComponentTypeId, QueryLockType>> {
     let mut lock_map = HashMap::new();
+    let mut ors = HashMap::new();
     for &(cty, ctf) in filters.iter() {
         match ctf {
-            QueryFilterType::With => {
+            QueryFilterType::With | QueryFilterType::Changed | QueryFilterType::Added => {
                 if !group.has(cty) {
                     return None;
                 }
@@ -205,11 +227,17 @@ fn access_filter(
                     return None;
                 }
             }
+            QueryFilterType::Or(idx) => {
+                *ors.entry(idx).or_insert(false) |= group.has(cty);
+            }
         }
     }
+    if ors.values().any(|&has| !has) {
+        return None;
+    }
     for &(cty, ctq) in accesses.iter() {
         match ctq {
-            QueryAccessType::Read | QueryAccessType::Write => {
+            QueryAccessType::Read | QueryAccessType::Write | QueryAccessType::LiveRead => {
                 if !group.has(cty) {
                     return None;
                 }
@@ -220,10 +248,15 @@ fn access_filter(
                 }
             }
         }
-        lock_map.insert(cty, ctq.into_lock());
+        //  The same component can be accessed more than once, such as with `Prev` and `Live`.
+        let lock = lock_map.entry(cty).or_insert(QueryLockType::Read);
+        if let QueryLockType::Write = ctq.into_lock() {
+            *lock = QueryLockType::Write;
+        }
     }
 
-    if lock_map.is_empty() {
+    //  Queries with only `Entity` or `Has` match every group.
+    if lock_map.is_empty() && !accesses.is_empty() {
         None?
     }
     Some(lock_map)
@@ -19,13 +19,18 @@ impl StorageBloc {
                     let ty = planet.get_type(cty)?;
                     Ok((
                         cty,
-                        match ty.dup {
-                            ValueDuplicate::None | ValueDuplicate::Clone(_) => {
-                                StorageRow::Normal(RwLock::new(DVec::new(ty.size, ty.drop)))
+                        match (ty.dup, planet.get_storage(cty)?) {
+                            (ValueDuplicate::Copy, ComponentStorage::DoubleBuffered) => {
+                                StorageRow::CopyCat(
+                                    Mutex::new(DVec::new(ty.size, ty.drop)),
+                                    DVec::new(ty.size, ty.drop),
+                                    RowTicks::new(),
+                                    RowTicks::new(),
+                                )
                             }
-                            ValueDuplicate::Copy => StorageRow::CopyCat(
-                                Mutex::new(DVec::new(ty.size, ty.drop)),
-                                DVec::new(ty.size, ty.drop),
+                            _ => StorageRow::Normal(
+                                RwLock::new(DVec::new(ty.size, ty.drop)),
+                                RowTicks::new(),
                             ),
                         },
                     ))
@@ -76,6 +81,26 @@ impl StorageBloc {
         Some(())
     }
 
+    pub fn get_try_write_lock(&self, id: ComponentTypeId) -> Option<bool> {
+        Some(
+            self.datas
+                .get(self.type_column(id)?)
+                .unwrap()
+                .1
+                .try_write_lock(),
+        )
+    }
+
+    pub fn get_try_read_lock(&self, id: ComponentTypeId) -> Option<bool> {
+        Some(
+            self.datas
+                .get(self.type_column(id)?)
+                .unwrap()
+                .1
+                .try_read_lock(),
+        )
+    }
+
     pub fn get_write(&self, id: ComponentTypeId) -> Option<*const u8> {
         Some(
             self.datas
@@ -96,6 +121,16 @@ impl StorageBloc {
         )
     }
 
+    pub fn get_ticks(&self, id: ComponentTypeId, write: bool) -> Option<*const RowTicks> {
+        Some(
+            self.datas
+                .get(self.type_column(id)?)
+                .unwrap()
+                .1
+                .access_ticks(write),
+        )
+    }
+
     pub fn get_entities(&self) -> *const Entity {
         self.entities.as_ptr()
     }
@@ -105,11 +140,11 @@ impl StorageBloc {
         //  TODO FIX: Should never panic. It's here right now just in case.
         assert!(self.entity_row(entity).is_none());
         self.entities.push(entity);
-        for (id, val) in ins.components.into_iter() {
+        for (id, (val, ticks)) in ins.components.into_iter() {
             let column = self.type_column(id).ok_or_else(|| {
                 ecs_err!(ErrorType::StorageBlocInsertComponent { id, entity }, self)
             })?;
-            self.datas.get_mut(column).unwrap().1.resize(1, val);
+            self.datas.get_mut(column).unwrap().1.resize(1, val, ticks);
         }
         Ok(())
     }
@@ -118,7 +153,7 @@ impl StorageBloc {
         let row = self
             .entity_row(entity)
             .ok_or(ecs_err!(ErrorType::StorageBlocRemove { entity }, self))?;
-        self.entities.remove(row);
+        self.entities.swap_remove(row);
         for data in self.datas.iter_mut() {
             data.1.swap_remove(row);
         }
@@ -130,13 +165,34 @@ impl StorageBloc {
         let row = self
             .entity_row(entity)
             .ok_or(ecs_err!(ErrorType::StorageBlocRemove { entity }, self))?;
-        self.entities.remove(row);
+        self.entities.swap_remove(row);
         for data in self.datas.iter_mut() {
             data.1.take_swap_remove(row);
         }
         Ok(())
     }
 
+    //  For components that are inserted again.
+    pub fn replace_component(
+        &mut self,
+        entity: Entity,
+        id: ComponentTypeId,
+        val: *const u8,
+        tick: u64,
+    ) -> Result<()> {
+        let row = self.entity_row(entity);
+        let column = self.type_column(id);
+        row.zip(column)
+            .and_then(|(row, column)| {
+                self.datas
+                    .get_mut(column)
+                    .unwrap()
+                    .1
+                    .replace(row, val, tick)
+            })
+            .ok_or_else(|| ecs_err!(ErrorType::StorageBlocReplaceComponent { entity, id }, self))
+    }
+
     pub(super) fn copy_entity(
         src: &mut Self,
         dst: &mut Self,
@@ -148,7 +204,8 @@ impl StorageBloc {
             (&src, &dst)
         ))?;
         for (id, data) in src.datas.iter_mut() {
-            missings.insert(*id, data.get_mut(src_row).unwrap());
+            let ticks = data.get_ticks(src_row);
+            missings.insert_with_ticks(*id, data.get_mut(src_row).unwrap(), ticks);
         }
         dst.insert_entity(entity, missings)?;
         src.take_remove_entity(entity)?;
@@ -160,9 +217,9 @@ impl StorageBloc {
         self.entity_row(entity)
     }
 
-    pub fn update(&mut self) {
+    pub fn update(&mut self, tick: u64, last_update: u64) {
         for (_, row) in self.datas.iter_mut() {
-            row.update();
+            row.update(tick, last_update);
         }
     }
 }
@@ -178,12 +235,15 @@ impl StorageBloc {
 }
 
 pub(super) struct StorageBlocInsert {
-    components: HashMap<ComponentTypeId, *const u8>,
+    tick: u64,
+    components: HashMap<ComponentTypeId, (*const u8, ComponentTicks)>,
 }
 
 impl StorageBlocInsert {
-    pub fn new() -> Self {
+    //  Newly inserted components are added and changed at `tick`.
+    pub fn new(tick: u64) -> Self {
         StorageBlocInsert {
+            tick,
             components: HashMap::new(),
         }
     }
@@ -191,6 +251,17 @@ impl StorageBlocInsert {
     //  A double insert results in one value being replaced.
     //  Do we want this behavior?
     pub fn insert(&mut self, id: ComponentTypeId, val: *const u8) {
-        self.components.insert(id, val);
+        self.components
+            .insert(id, (val, ComponentTicks::new(self.tick)));
+    }
+
+    //  For components moving between storages, which keep their ticks.
+    pub fn insert_with_ticks(
+        &mut self,
+        id: ComponentTypeId,
+        val: *const u8,
+        ticks: ComponentTicks,
+    ) {
+        self.components.insert(id, (val, ticks));
     }
 }
@@ -1,6 +1,6 @@
 use super::{
-    error::*, ComponentGroup, ComponentGroupId, ComponentGroupPlanet, ComponentTypeId,
-    ComponentTypePlanet, Entity, EntityPlanet, QueryPlanet,
+    error::*, ComponentGroup, ComponentGroupId, ComponentGroupPlanet, ComponentStorage,
+    ComponentTypeId, ComponentTypePlanet, Entity, EntityPlanet, QueryPlanet,
 };
 use crate::data::{DVec, SparseSet, TVal, ValueDuplicate};
 use parking_lot::{Mutex, RwLock};
@@ -11,15 +11,18 @@ type Row = usize;
 
 mod bloc;
 mod row;
+mod tick;
 
 use bloc::{StorageBloc, StorageBlocInsert};
 use row::StorageRow;
+pub use tick::{ComponentTicks, RowTicks};
 
 #[derive(Debug)]
 pub struct StoragePlanet {
     null_group: ComponentGroupId,
     storages: SparseSet<ComponentGroupId, StorageBloc>,
     entities: HashMap<Entity, ComponentGroupId>,
+    last_update: u64,
 }
 
 impl StoragePlanet {
@@ -37,6 +40,7 @@ impl StoragePlanet {
             null_group,
             storages,
             entities: HashMap::new(),
+            last_update: 0,
         })
     }
 
@@ -51,7 +55,7 @@ impl StoragePlanet {
             self.storages
                 .get_mut(self.null_group.id())
                 .unwrap()
-                .insert_entity(entity, StorageBlocInsert::new())?;
+                .insert_entity(entity, StorageBlocInsert::new(0))?;
             Ok(())
         }
     }
@@ -79,6 +83,7 @@ impl StoragePlanet {
         query_planet: &mut QueryPlanet,
         entity: Entity,
         modify: StorageModifyTransform,
+        tick: u64,
     ) -> Result<()> {
         let old_gid = *self.entities.get(&entity).ok_or(ecs_err!(
             ErrorType::StoragePlanetTransformEntity { entity },
@@ -91,15 +96,28 @@ impl StoragePlanet {
                 (&self, cty_planet)
             ))?
             .clone();
+        //  Components that the entity already has are replaced where they are.
+        let replaces = modify
+            .inserts
+            .iter()
+            .map(|(insert, _)| group.has(*insert))
+            .collect::<Vec<_>>();
         let mut group_modify = group.modify();
 
-        let mut missings = StorageBlocInsert::new();
+        let mut missings = StorageBlocInsert::new(tick);
 
         for remove in modify.removes {
             group_modify.remove(remove);
         }
-        for (insert, val) in modify.inserts.iter() {
-            missings.insert(*insert, val.get());
+        for ((insert, val), &replace) in modify.inserts.iter().zip(replaces.iter()) {
+            if replace {
+                self.storages
+                    .get_mut(old_gid.id())
+                    .unwrap()
+                    .replace_component(entity, *insert, val.get(), tick)?;
+            } else {
+                missings.insert(*insert, val.get());
+            }
             group_modify.insert(*insert);
         }
         group_modify.build();
@@ -115,17 +133,17 @@ impl StoragePlanet {
             new_gid
         };
 
-        //  <Danger Zone>
-        let mut src_storage = unsafe {
-            std::ptr::read(self.storages.get(old_gid.id()).unwrap() as *const StorageBloc)
-        };
-        let dst_storage = self.storages.get_mut(new_gid.id()).unwrap();
-        let res = StorageBloc::copy_entity(&mut src_storage, dst_storage, entity, missings);
-        std::mem::forget(src_storage);
-        res?;
-        //  </Danger Zone>
+        //  Otherwise, there's nothing to move, and the storages below would alias.
+        if new_gid != old_gid {
+            //  <Danger Zone>
+            //  A pointer rather than a copy, otherwise lengths in the source storage go stale.
+            let src_storage = self.storages.get_mut(old_gid.id()).unwrap() as *mut StorageBloc;
+            let dst_storage = self.storages.get_mut(new_gid.id()).unwrap();
+            StorageBloc::copy_entity(unsafe { &mut *src_storage }, dst_storage, entity, missings)?;
+            //  </Danger Zone>
 
-        *self.entities.get_mut(&entity).unwrap() = new_gid;
+            *self.entities.get_mut(&entity).unwrap() = new_gid;
+        }
 
         for (_, val) in modify.inserts {
             val.take();
@@ -141,15 +159,19 @@ impl StoragePlanet {
         cg_planet: &mut ComponentGroupPlanet,
         query_planet: &mut QueryPlanet,
         trans: StorageTransform,
+        tick: u64,
     ) -> Result<()> {
         match trans {
             StorageTransform::Insert(entity, modify) => {
                 self.insert_entity(entity)?;
-                self.modify(cty_planet, cg_planet, query_planet, entity, modify)?;
+                //  Otherwise, the entity would be copied from the null storage into itself.
+                if !modify.is_empty() {
+                    self.modify(cty_planet, cg_planet, query_planet, entity, modify, tick)?;
+                }
             }
             StorageTransform::Modify(entity, modify) => {
                 if !modify.is_empty() {
-                    self.modify(cty_planet, cg_planet, query_planet, entity, modify)?;
+                    self.modify(cty_planet, cg_planet, query_planet, entity, modify, tick)?;
                 }
             }
             StorageTransform::Remove(entity) => {
@@ -220,6 +242,28 @@ impl StoragePlanet {
             .map(|storage| storage.get_read_unlock(cid))
     }
 
+    pub fn get_try_write_lock(
+        &self,
+        gid: ComponentGroupId,
+        cid: ComponentTypeId,
+    ) -> Result<Option<bool>> {
+        self.storages
+            .get(gid.id())
+            .ok_or(ecs_err!(ErrorType::StoragePlanetAccess { id: gid }, self))
+            .map(|storage| storage.get_try_write_lock(cid))
+    }
+
+    pub fn get_try_read_lock(
+        &self,
+        gid: ComponentGroupId,
+        cid: ComponentTypeId,
+    ) -> Result<Option<bool>> {
+        self.storages
+            .get(gid.id())
+            .ok_or(ecs_err!(ErrorType::StoragePlanetAccess { id: gid }, self))
+            .map(|storage| storage.get_try_read_lock(cid))
+    }
+
     pub fn get_write(&self, gid: ComponentGroupId, cid: ComponentTypeId) -> Option<*const u8> {
         self.storages.get(gid.id()).unwrap().get_write(cid)
     }
@@ -228,6 +272,15 @@ impl StoragePlanet {
         self.storages.get(gid.id()).unwrap().get_read(cid)
     }
 
+    pub fn get_ticks(
+        &self,
+        gid: ComponentGroupId,
+        cid: ComponentTypeId,
+        write: bool,
+    ) -> Option<*const RowTicks> {
+        self.storages.get(gid.id()).unwrap().get_ticks(cid, write)
+    }
+
     pub fn get_entities(&self, gid: ComponentGroupId) -> Option<*const Entity> {
         Some(self.storages.get(gid.id())?.get_entities())
     }
@@ -240,10 +293,11 @@ impl StoragePlanet {
         self.storages.get(group.id())?.get_entity_idx(entity)
     }
 
-    pub fn update(&mut self) {
+    pub fn update(&mut self, tick: u64) {
         for (_, bloc) in self.storages.get_mut_dense() {
-            bloc.update();
+            bloc.update(tick, self.last_update);
         }
+        self.last_update = tick;
     }
 }
 
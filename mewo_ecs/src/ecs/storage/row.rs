@@ -2,104 +2,226 @@ use super::*;
 
 #[derive(Debug)]
 pub(super) enum StorageRow {
-    Normal(RwLock<DVec>),
-    CopyCat(Mutex<DVec>, DVec),
+    Normal(RwLock<DVec>, RowTicks),
+    //  Write, read, then their ticks in the same order.
+    CopyCat(Mutex<DVec>, DVec, RowTicks, RowTicks),
 }
 
 impl StorageRow {
-    pub fn update(&mut self) {
-        if let StorageRow::CopyCat(write, read) = self {
+    //  Only rows marked dirty in the write ticks are copied over.
+    //  Every `&mut` access stamps its row, so anything else is already the same in both.
+    pub fn update(&mut self, tick: u64, last_update: u64) {
+        if let StorageRow::CopyCat(write, read, write_ticks, read_ticks) = self {
+            let dirty = write_ticks.take_dirty();
+            read_ticks.update_from(write_ticks, dirty.clone(), tick, last_update);
             //  Assumes that the size of write is always >= read.
-            let write = write.lock();
+            let write = write.get_mut();
             unsafe {
                 if write.len() < read.len() {
                     read.unsafe_truncate(write.len());
                 } else {
                     read.resize_zeroed(write.len() - read.len());
                 }
-                std::ptr::copy_nonoverlapping(
-                    write.ptr(),
-                    read.ptr() as *mut u8,
-                    write.len() * write.size(),
-                );
+                if let Some(dirty) = dirty {
+                    let size = write.size();
+                    std::ptr::copy_nonoverlapping(
+                        write.ptr().add(dirty.start * size),
+                        (read.ptr() as *mut u8).add(dirty.start * size),
+                        dirty.len() * size,
+                    );
+                }
             };
         }
     }
 
     pub fn access_write(&self) -> *const u8 {
         match self {
-            StorageRow::Normal(v) => unsafe { &*v.data_ptr() }.ptr(),
-            StorageRow::CopyCat(v, _) => unsafe { &*v.data_ptr() }.ptr(),
+            StorageRow::Normal(v, _) => unsafe { &*v.data_ptr() }.ptr(),
+            StorageRow::CopyCat(v, _, _, _) => unsafe { &*v.data_ptr() }.ptr(),
         }
     }
 
     pub fn access_read(&self) -> *const u8 {
         match self {
-            StorageRow::Normal(v) => unsafe { &*v.data_ptr() }.ptr(),
-            StorageRow::CopyCat(_, v) => v.ptr(),
+            StorageRow::Normal(v, _) => unsafe { &*v.data_ptr() }.ptr(),
+            StorageRow::CopyCat(_, v, _, _) => v.ptr(),
+        }
+    }
+
+    //  The ticks that go along with `access_write` or `access_read`.
+    pub fn access_ticks(&self, write: bool) -> *const RowTicks {
+        match self {
+            StorageRow::Normal(_, t) => t,
+            StorageRow::CopyCat(_, _, t, _) if write => t,
+            StorageRow::CopyCat(_, _, _, t) => t,
         }
     }
 
     pub fn write_lock(&self) {
         match self {
-            StorageRow::Normal(v) => std::mem::forget(v.write()),
-            StorageRow::CopyCat(v, _) => std::mem::forget(v.lock()),
+            StorageRow::Normal(v, _) => std::mem::forget(v.write()),
+            StorageRow::CopyCat(v, _, _, _) => std::mem::forget(v.lock()),
         }
     }
 
     pub fn write_unlock(&self) {
         match self {
-            StorageRow::Normal(v) => unsafe { v.force_unlock_write() },
-            StorageRow::CopyCat(v, _) => unsafe { v.force_unlock() },
+            StorageRow::Normal(v, _) => unsafe { v.force_unlock_write() },
+            StorageRow::CopyCat(v, _, _, _) => unsafe { v.force_unlock() },
         }
     }
 
     pub fn read_lock(&self) {
-        if let StorageRow::Normal(v) = self {
+        if let StorageRow::Normal(v, _) = self {
             std::mem::forget(v.read())
         };
     }
 
     pub fn read_unlock(&self) {
-        if let StorageRow::Normal(v) = self {
+        if let StorageRow::Normal(v, _) = self {
             unsafe { v.force_unlock_read() }
         };
     }
 
+    //  Like `write_lock` and `read_lock`, but `false` instead of blocking.
+    pub fn try_write_lock(&self) -> bool {
+        match self {
+            StorageRow::Normal(v, _) => v.try_write().map(std::mem::forget).is_some(),
+            StorageRow::CopyCat(v, _, _, _) => v.try_lock().map(std::mem::forget).is_some(),
+        }
+    }
+
+    pub fn try_read_lock(&self) -> bool {
+        match self {
+            StorageRow::Normal(v, _) => v.try_read().map(std::mem::forget).is_some(),
+            StorageRow::CopyCat(..) => true,
+        }
+    }
+
     pub fn swap_remove(&mut self, idx: usize) {
         match self {
-            StorageRow::Normal(v) => v.write().swap_remove(idx),
-            StorageRow::CopyCat(v, _) => v.lock().swap_remove(idx),
+            StorageRow::Normal(v, t) => {
+                v.write().swap_remove(idx);
+                t.swap_remove(idx);
+            }
+            StorageRow::CopyCat(v, _, t, _) => {
+                v.lock().swap_remove(idx);
+                t.swap_remove(idx);
+            }
         };
     }
 
     pub fn take_swap_remove(&mut self, idx: usize) {
         match self {
-            StorageRow::Normal(v) => v.write().take_swap_remove(idx),
-            StorageRow::CopyCat(v, _) => v.lock().take_swap_remove(idx),
+            StorageRow::Normal(v, t) => {
+                v.write().take_swap_remove(idx);
+                t.swap_remove(idx);
+            }
+            StorageRow::CopyCat(v, _, t, _) => {
+                v.lock().take_swap_remove(idx);
+                t.swap_remove(idx);
+            }
         };
     }
 
+    //  The old value is dropped, and the new one counts as changed at `tick`.
+    pub fn replace(&mut self, idx: usize, inplace: *const u8, tick: u64) -> Option<()> {
+        match self {
+            StorageRow::Normal(v, t) => {
+                unsafe { v.write().replace(idx, inplace) }?;
+                t.set_changed(idx, tick);
+            }
+            StorageRow::CopyCat(v, _, t, _) => {
+                unsafe { v.lock().replace(idx, inplace) }?;
+                t.set_changed(idx, tick);
+            }
+        }
+        Some(())
+    }
+
     pub fn get_mut(&mut self, idx: usize) -> Option<*mut u8> {
         match self {
-            StorageRow::Normal(v) => v.write().get(idx).map(|ptr| ptr as *mut u8),
-            StorageRow::CopyCat(v, _) => v.lock().get(idx).map(|ptr| ptr as *mut u8),
+            StorageRow::Normal(v, _) => v.write().get(idx).map(|ptr| ptr as *mut u8),
+            StorageRow::CopyCat(v, _, t, _) => {
+                t.mark_dirty(idx);
+                v.lock().get(idx).map(|ptr| ptr as *mut u8)
+            }
+        }
+    }
+
+    pub fn get_ticks(&self, idx: usize) -> ComponentTicks {
+        match self {
+            StorageRow::Normal(_, t) | StorageRow::CopyCat(_, _, t, _) => t.get(idx),
         }
     }
 
-    pub fn resize(&mut self, idx: usize, inplace: *const u8) {
+    pub fn resize(&mut self, idx: usize, inplace: *const u8, ticks: ComponentTicks) {
         match self {
-            StorageRow::Normal(v) => unsafe { v.write().resize(idx, inplace) },
-            StorageRow::CopyCat(v, _) => unsafe { v.lock().resize(idx, inplace) },
+            StorageRow::Normal(v, t) => {
+                unsafe { v.write().resize(idx, inplace) };
+                t.push(ticks);
+            }
+            StorageRow::CopyCat(v, _, t, _) => {
+                unsafe { v.lock().resize(idx, inplace) };
+                t.push(ticks);
+            }
         }
     }
 
     //  For copycat, the len of copy is returned.
+    //  Lengths only change with `&mut self`, so this doesn't need to wait for the lock, which
+    //  the caller may hold.
     pub fn get_len(&self) -> usize {
-        let r = match self {
-            StorageRow::Normal(v) => v.read().len(),
-            StorageRow::CopyCat(_, v) => v.len(),
-        };
-        r
+        match self {
+            StorageRow::Normal(v, _) => unsafe { &*v.data_ptr() }.len(),
+            StorageRow::CopyCat(_, v, _, _) => v.len(),
+        }
     }
 }
+
+#[test]
+fn test_copycat_update_dirty() {
+    use crate::data::ValueDrop;
+
+    let size = std::mem::size_of::<u64>();
+    let mut row = StorageRow::CopyCat(
+        Mutex::new(DVec::new(size, ValueDrop::empty())),
+        DVec::new(size, ValueDrop::empty()),
+        RowTicks::new(),
+        RowTicks::new(),
+    );
+    let read =
+        |row: &StorageRow, idx: usize| unsafe { *(row.access_read() as *const u64).add(idx) };
+    let read_ticks = |row: &StorageRow, idx: usize| unsafe { &*row.access_ticks(false) }.get(idx);
+    let write = |row: &StorageRow, idx: usize, val: u64| unsafe {
+        *(row.access_write() as *mut u64).add(idx) = val;
+    };
+
+    for val in [10u64, 20] {
+        row.resize(1, &val as *const u64 as *const u8, ComponentTicks::new(1));
+    }
+    row.update(2, 1);
+    assert_eq!((read(&row, 0), read(&row, 1)), (10, 20));
+    assert_eq!(read_ticks(&row, 0), ComponentTicks::new(1));
+
+    //  Written behind the row's back, so nothing is dirty and nothing is copied.
+    write(&row, 0, 11);
+    row.update(3, 2);
+    assert_eq!((read(&row, 0), read(&row, 1)), (10, 20));
+    assert_eq!(read_ticks(&row, 0), ComponentTicks::new(1));
+    assert_eq!(read_ticks(&row, 1), ComponentTicks::new(1));
+
+    //  Only the stamped row is copied, along with its ticks.
+    write(&row, 1, 21);
+    unsafe { &*row.access_ticks(true) }.set_changed(1, 4);
+    row.update(5, 3);
+    assert_eq!((read(&row, 0), read(&row, 1)), (10, 21));
+    assert_eq!(read_ticks(&row, 0), ComponentTicks::new(1));
+    assert_eq!(
+        read_ticks(&row, 1),
+        ComponentTicks {
+            added: 1,
+            changed: 5
+        }
+    );
+}
@@ -0,0 +1,116 @@
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+//  Ticks come from `Galaxy`'s change tick which starts at 1, so 0 means never.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ComponentTicks {
+    pub added: u64,
+    pub changed: u64,
+}
+
+impl ComponentTicks {
+    pub fn new(tick: u64) -> Self {
+        ComponentTicks {
+            added: tick,
+            changed: tick,
+        }
+    }
+}
+
+//  Parallel to a row's `DVec`.
+//  Atomic so that `&mut` accesses can stamp through a shared `StoragePlanet`.
+//  They hold the component's write lock while doing so anyway.
+//  Rows that were stamped, pushed, or moved since the last `take_dirty` are kept as a range.
+#[derive(Debug)]
+pub struct RowTicks {
+    added: Vec<AtomicU64>,
+    changed: Vec<AtomicU64>,
+    dirty_start: AtomicUsize,
+    dirty_end: AtomicUsize,
+}
+
+impl Default for RowTicks {
+    fn default() -> Self {
+        RowTicks {
+            added: Vec::new(),
+            changed: Vec::new(),
+            dirty_start: AtomicUsize::new(usize::MAX),
+            dirty_end: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl RowTicks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, ticks: ComponentTicks) {
+        self.added.push(AtomicU64::new(ticks.added));
+        self.changed.push(AtomicU64::new(ticks.changed));
+        self.mark_dirty(self.added.len() - 1);
+    }
+
+    pub fn swap_remove(&mut self, idx: usize) {
+        self.added.swap_remove(idx);
+        self.changed.swap_remove(idx);
+        //  The last row now lives at `idx`.
+        if idx < self.added.len() {
+            self.mark_dirty(idx);
+        }
+    }
+
+    pub fn get(&self, idx: usize) -> ComponentTicks {
+        ComponentTicks {
+            added: self.added[idx].load(Ordering::Relaxed),
+            changed: self.changed[idx].load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn set_changed(&self, idx: usize, tick: u64) {
+        self.changed[idx].store(tick, Ordering::Relaxed);
+        self.mark_dirty(idx);
+    }
+
+    pub fn mark_dirty(&self, idx: usize) {
+        self.dirty_start.fetch_min(idx, Ordering::Relaxed);
+        self.dirty_end.fetch_max(idx + 1, Ordering::Relaxed);
+    }
+
+    //  The dirty rows, clamped to the current length, then starts over with none.
+    pub fn take_dirty(&mut self) -> Option<Range<usize>> {
+        let start = std::mem::replace(self.dirty_start.get_mut(), usize::MAX);
+        let end = std::mem::replace(self.dirty_end.get_mut(), 0).min(self.added.len());
+        (start < end).then_some(start..end)
+    }
+
+    //  Copy the `dirty` rows of `write` into `self`, but ticks newer than `last_update` become
+    //  `tick`.
+    //  That way, copycat reads see a change only once it's visible.
+    //  Other rows weren't touched, so only the length is brought along.
+    pub fn update_from(
+        &mut self,
+        write: &RowTicks,
+        dirty: Option<Range<usize>>,
+        tick: u64,
+        last_update: u64,
+    ) {
+        for (dst, src) in [
+            (&mut self.added, &write.added),
+            (&mut self.changed, &write.changed),
+        ] {
+            dst.truncate(src.len());
+            dst.resize_with(src.len(), AtomicU64::default);
+            let dirty = match dirty.clone() {
+                Some(dirty) => dirty,
+                None => continue,
+            };
+            for (dst, src) in dst[dirty.clone()].iter_mut().zip(src[dirty].iter()) {
+                let src = src.load(Ordering::Relaxed);
+                *dst.get_mut() = if src > last_update { tick } else { src };
+            }
+        }
+    }
+}
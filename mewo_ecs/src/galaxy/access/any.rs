@@ -0,0 +1,99 @@
+use super::*;
+
+/// Tuples of `&C` and `&mut C` that can go in an [`AnyOf`].
+pub trait ComponentAccessesAnyOf {
+    type Options: ComponentAccessesOptional;
+    /// `Self`, but borrowed for `'a`.
+    type Item<'a>: ComponentAccessesAnyOf;
+}
+
+/// `&C` or `&mut C` in an [`AnyOf`].
+pub trait ComponentAccessAnyOf {
+    type Option: ComponentAccessOptional;
+    type Item<'a>: ComponentAccessAnyOf;
+}
+
+impl<C> ComponentAccessAnyOf for &C
+where
+    C: GenericComponent + 'static,
+{
+    type Option = Option<Self>;
+    type Item<'a> = &'a C;
+}
+
+impl<C> ComponentAccessAnyOf for &mut C
+where
+    C: GenericComponent + 'static,
+{
+    type Option = Option<Self>;
+    type Item<'a> = &'a mut C;
+}
+
+/// Matches entities with at least one of the components in `CA`, each of which is given as an
+/// `Option`.
+///
+/// ```rust,ignore
+/// for AnyOf((sprite, text)) in g.query::<AnyOf<(&Sprite, &Text)>>().iter() {
+///     //  ...
+/// }
+/// ```
+pub struct AnyOf<CA: ComponentAccessesAnyOf>(pub CA::Options);
+
+impl<CA> ComponentAccessesReadOnly for AnyOf<CA>
+where
+    CA: ComponentAccessesAnyOf,
+    CA::Options: ComponentAccessesReadOnly,
+{
+}
+
+impl<CA> ComponentAccessesOptional for AnyOf<CA>
+where
+    CA: ComponentAccessesAnyOf,
+{
+    const COUNT: usize = CA::Options::COUNT;
+
+    type Item<'a> = AnyOf<CA::Item<'a>>;
+
+    fn fetches() -> Vec<QueryFetch> {
+        CA::Options::fetches()
+    }
+
+    fn datas(datas: &[Option<*const u8>], idx: usize) -> Self {
+        AnyOf(CA::Options::datas(datas, idx))
+    }
+
+    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
+        CA::Options::component_maybe_insert(ctyp)
+    }
+
+    fn any_ofs() -> Vec<Vec<ComponentTypeId>> {
+        let mut any_ofs = CA::Options::any_ofs();
+        any_ofs.push(Self::infos().into_iter().map(|(cty, _)| cty).collect());
+        any_ofs
+    }
+}
+
+macro_rules! impl_component_accesses_any_of {
+    ($($c:ident),*) => {
+        impl<$($c),*> ComponentAccessesAnyOf for ($($c,)*)
+        where
+            $($c: ComponentAccessAnyOf),*
+        {
+            type Options = ($($c::Option,)*);
+            type Item<'a> = ($($c::Item<'a>,)*);
+        }
+    };
+}
+
+impl_component_accesses_any_of!(C0);
+impl_component_accesses_any_of!(C0, C1);
+impl_component_accesses_any_of!(C0, C1, C2);
+impl_component_accesses_any_of!(C0, C1, C2, C3);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5, C6);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5, C6, C7);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5, C6, C7, C8);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10);
+impl_component_accesses_any_of!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11);
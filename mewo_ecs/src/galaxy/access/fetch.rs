@@ -0,0 +1,73 @@
+use super::*;
+use std::{marker::PhantomData, ops::Deref};
+
+/// What a query fetches for each value it yields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryFetch {
+    //  Locked and read from storage.
+    Access(ComponentTypeId, QueryAccessType),
+    //  Resolved from the group alone, so never locked.
+    Has(ComponentTypeId),
+    Entity,
+}
+
+/// Whether the entity has `C`, without accessing `C`.
+/// Derefs to `bool`.
+///
+/// ```rust,ignore
+/// for (entity, player, frozen) in g.query::<(Entity, &mut Player, Has<Frozen>)>().iter() {
+///     if !*frozen {
+///         //  ...
+///     }
+/// }
+/// ```
+pub struct Has<C>(bool, PhantomData<C>);
+
+impl<C> Deref for Has<C> {
+    type Target = bool;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C> ComponentAccessesReadOnly for Has<C> where C: GenericComponent + 'static {}
+
+impl<C> ComponentAccessesOptional for Has<C>
+where
+    C: GenericComponent + 'static,
+{
+    const COUNT: usize = 1;
+
+    type Item<'a> = Has<C>;
+
+    fn fetches() -> Vec<QueryFetch> {
+        vec![QueryFetch::Has(C::mewo_component_id())]
+    }
+
+    //  `Some` when the group has `C`, the pointer itself is meaningless.
+    fn datas(datas: &[Option<*const u8>], _idx: usize) -> Self {
+        Has(datas[0].is_some(), PhantomData)
+    }
+
+    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
+        component_maybe_insert::<C>(ctyp)
+    }
+}
+
+impl ComponentAccessesReadOnly for Entity {}
+
+impl ComponentAccessesOptional for Entity {
+    const COUNT: usize = 1;
+
+    type Item<'a> = Entity;
+
+    fn fetches() -> Vec<QueryFetch> {
+        vec![QueryFetch::Entity]
+    }
+
+    fn datas(datas: &[Option<*const u8>], idx: usize) -> Self {
+        unsafe { *(datas[0].unwrap() as *const Entity).add(idx) }
+    }
+
+    fn component_maybe_insert(_ctyp: &RwLock<ComponentTypePlanet>) {}
+}
@@ -0,0 +1,75 @@
+use super::*;
+use std::ops::Deref;
+
+/// `C` as of the last update.
+/// This is what `&C` already reads for [`CheapComponent`](crate::CheapComponent)s, since writes
+/// to them don't show until the next update.
+/// Other components aren't double buffered, so this is the same as [`Live`].
+///
+/// ```rust,ignore
+/// for (prev, live) in g.query::<(Prev<&Position>, Live<&Position>)>().iter() {
+///     let velocity = live.0 - prev.0;
+/// }
+/// ```
+pub struct Prev<T>(pub T);
+
+/// `C` including this frame's writes, which `&C` can't see for
+/// [`CheapComponent`](crate::CheapComponent)s.
+/// This locks `C` the same way as `&mut C`, but doesn't mark it as changed.
+pub struct Live<T>(pub T);
+
+impl<C> Deref for Prev<&C> {
+    type Target = C;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<C> Deref for Live<&C> {
+    type Target = C;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<C> ComponentAccessReadOnly for Prev<&C> where C: GenericComponent + 'static {}
+
+impl<C> ComponentAccessOptional for Prev<&C>
+where
+    C: GenericComponent + 'static,
+{
+    type Item<'a> = Prev<&'a C>;
+
+    fn info() -> (ComponentTypeId, QueryAccessType) {
+        (C::mewo_component_id(), QueryAccessType::Read)
+    }
+
+    fn data(data: Option<*const u8>, idx: usize) -> Self {
+        Prev(<&C as ComponentAccessOptional>::data(data, idx))
+    }
+
+    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
+        component_maybe_insert::<C>(ctyp)
+    }
+}
+
+impl<C> ComponentAccessReadOnly for Live<&C> where C: GenericComponent + 'static {}
+
+impl<C> ComponentAccessOptional for Live<&C>
+where
+    C: GenericComponent + 'static,
+{
+    type Item<'a> = Live<&'a C>;
+
+    fn info() -> (ComponentTypeId, QueryAccessType) {
+        (C::mewo_component_id(), QueryAccessType::LiveRead)
+    }
+
+    fn data(data: Option<*const u8>, idx: usize) -> Self {
+        Live(<&C as ComponentAccessOptional>::data(data, idx))
+    }
+
+    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
+        component_maybe_insert::<C>(ctyp)
+    }
+}
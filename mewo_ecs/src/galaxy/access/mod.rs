@@ -1,26 +1,37 @@
-use super::{ComponentTypeId, ComponentTypePlanet, GenericComponent, QueryAccessType};
+use super::{ComponentTypeId, ComponentTypePlanet, Entity, GenericComponent, QueryAccessType};
 use parking_lot::RwLock;
 
-//  TODO EXT: Use macros!
-
 //  Used by filter.
 mod normal;
 
 //  Used by query.
+mod any;
+mod fetch;
+mod frame;
 mod optional;
 
 //  Used by entity get.
 // mod nonoptional;
 
 // pub use nonoptional::{ComponentAccessNonOptional, ComponentAccessesNonOptional};
+pub use any::{AnyOf, ComponentAccessAnyOf, ComponentAccessesAnyOf};
+pub use fetch::{Has, QueryFetch};
+pub use frame::{Live, Prev};
 pub use normal::ComponentAccessesNormal;
-pub use optional::{ComponentAccessesOptional, ComponentAccessOptional};
+use optional::{ComponentAccessOptional, ComponentAccessReadOnly};
+pub use optional::{ComponentAccessesOptional, ComponentAccessesReadOnly};
 
-fn component_maybe_insert<C: GenericComponent + 'static>(ctyp: &RwLock<ComponentTypePlanet>) {
+pub(super) fn component_maybe_insert<C: GenericComponent + 'static>(
+    ctyp: &RwLock<ComponentTypePlanet>,
+) {
     let id = C::mewo_component_id();
     if ctyp.read().get_type(id).is_err() {
         ctyp.write()
-            .insert_type(id, C::mewo_component_type_entry())
+            .insert_type(
+                id,
+                C::mewo_component_type_entry(),
+                C::mewo_component_storage(),
+            )
             .unwrap();
     }
 }
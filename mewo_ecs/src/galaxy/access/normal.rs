@@ -18,17 +18,34 @@ where
     }
 }
 
-impl<C0, C1> ComponentAccessesNormal for (C0, C1)
-where
-    C0: GenericComponent + 'static,
-    C1: GenericComponent + 'static,
-{
-    fn hashes() -> Vec<ComponentTypeId> {
-        vec![C0::mewo_component_id(), C1::mewo_component_id()]
-    }
+macro_rules! impl_component_accesses_normal {
+    ($($c:ident),*) => {
+        impl<$($c),*> ComponentAccessesNormal for ($($c,)*)
+        where
+            $($c: ComponentAccessesNormal),*
+        {
+            fn hashes() -> Vec<ComponentTypeId> {
+                let mut hashes = Vec::new();
+                $(hashes.append(&mut $c::hashes());)*
+                hashes
+            }
 
-    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
-        component_maybe_insert::<C0>(ctyp);
-        component_maybe_insert::<C1>(ctyp);
-    }
+            fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
+                $($c::component_maybe_insert(ctyp);)*
+            }
+        }
+    };
 }
+
+impl_component_accesses_normal!(C0);
+impl_component_accesses_normal!(C0, C1);
+impl_component_accesses_normal!(C0, C1, C2);
+impl_component_accesses_normal!(C0, C1, C2, C3);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5, C6);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5, C6, C7);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5, C6, C7, C8);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10);
+impl_component_accesses_normal!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11);
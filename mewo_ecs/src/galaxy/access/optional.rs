@@ -1,21 +1,58 @@
 use super::*;
 
 pub trait ComponentAccessOptional {
+    /// `Self`, but borrowed for `'a`.
+    type Item<'a>: ComponentAccessOptional;
+
     fn info() -> (ComponentTypeId, QueryAccessType);
     fn data(data: Option<*const u8>, idx: usize) -> Self;
     fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>);
 }
 
 pub trait ComponentAccessesOptional {
-    fn infos() -> Vec<(ComponentTypeId, QueryAccessType)>;
+    /// The number of values fetched, including those in nested tuples.
+    const COUNT: usize;
+
+    /// `Self`, but borrowed for `'a`, see `QueryInfo::for_each_combination`.
+    type Item<'a>: ComponentAccessesOptional;
+
+    //  One for each of `datas`, in order.
+    fn fetches() -> Vec<QueryFetch>;
+
+    //  Only the components that need to be locked.
+    fn infos() -> Vec<(ComponentTypeId, QueryAccessType)> {
+        Self::fetches()
+            .into_iter()
+            .filter_map(|fetch| match fetch {
+                QueryFetch::Access(cty, access) => Some((cty, access)),
+                _ => None,
+            })
+            .collect()
+    }
+
     fn datas(datas: &[Option<*const u8>], idx: usize) -> Self;
     fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>);
+
+    //  Each set of components must have at least one present, see `AnyOf`.
+    fn any_ofs() -> Vec<Vec<ComponentTypeId>> {
+        Vec::new()
+    }
 }
 
+/// Accesses that never write, so their items can be shared, see
+/// `QueryInfo::iter_combinations`.
+pub trait ComponentAccessesReadOnly: ComponentAccessesOptional {}
+
+pub trait ComponentAccessReadOnly: ComponentAccessOptional {}
+
+impl<C> ComponentAccessReadOnly for &C where C: GenericComponent + 'static {}
+
 impl<C> ComponentAccessOptional for &C
 where
     C: GenericComponent + 'static,
 {
+    type Item<'a> = &'a C;
+
     fn info() -> (ComponentTypeId, QueryAccessType) {
         (C::mewo_component_id(), QueryAccessType::Read)
     }
@@ -33,6 +70,8 @@ impl<C> ComponentAccessOptional for &mut C
 where
     C: GenericComponent + 'static,
 {
+    type Item<'a> = &'a mut C;
+
     fn info() -> (ComponentTypeId, QueryAccessType) {
         (C::mewo_component_id(), QueryAccessType::Write)
     }
@@ -51,10 +90,14 @@ where
     }
 }
 
+impl<C> ComponentAccessReadOnly for Option<&C> where C: GenericComponent + 'static {}
+
 impl<C> ComponentAccessOptional for Option<&C>
 where
     C: GenericComponent + 'static,
 {
+    type Item<'a> = Option<&'a C>;
+
     fn info() -> (ComponentTypeId, QueryAccessType) {
         (C::mewo_component_id(), QueryAccessType::OptionRead)
     }
@@ -72,6 +115,8 @@ impl<C> ComponentAccessOptional for Option<&mut C>
 where
     C: GenericComponent + 'static,
 {
+    type Item<'a> = Option<&'a mut C>;
+
     fn info() -> (ComponentTypeId, QueryAccessType) {
         (C::mewo_component_id(), QueryAccessType::OptionWrite)
     }
@@ -93,59 +138,80 @@ impl<C0> ComponentAccessesOptional for C0
 where
     C0: ComponentAccessOptional,
 {
-    fn infos() -> Vec<(ComponentTypeId, QueryAccessType)> {
-        vec![C0::info()]
-    }
+    const COUNT: usize = 1;
 
-    fn datas(datas: &[Option<*const u8>], idx: usize) -> Self {
-        C0::data(datas[0], idx)
-    }
+    type Item<'a> = C0::Item<'a>;
 
-    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
-        C0::component_maybe_insert(ctyp);
-    }
-}
-
-impl<C0, C1> ComponentAccessesOptional for (C0, C1)
-where
-    C0: ComponentAccessOptional,
-    C1: ComponentAccessOptional,
-{
-    fn infos() -> Vec<(ComponentTypeId, QueryAccessType)> {
-        vec![C0::info(), C1::info()]
+    fn fetches() -> Vec<QueryFetch> {
+        let (cty, access) = C0::info();
+        vec![QueryFetch::Access(cty, access)]
     }
 
     fn datas(datas: &[Option<*const u8>], idx: usize) -> Self {
-        (C0::data(datas[0], idx), C1::data(datas[1], idx))
+        C0::data(datas[0], idx)
     }
 
     fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
         C0::component_maybe_insert(ctyp);
-        C1::component_maybe_insert(ctyp);
     }
 }
 
-impl<C0, C1, C2> ComponentAccessesOptional for (C0, C1, C2)
-where
-    C0: ComponentAccessOptional,
-    C1: ComponentAccessOptional,
-    C2: ComponentAccessOptional,
-{
-    fn infos() -> Vec<(ComponentTypeId, QueryAccessType)> {
-        vec![C0::info(), C1::info(), C2::info()]
-    }
-
-    fn datas(datas: &[Option<*const u8>], idx: usize) -> Self {
-        (
-            C0::data(datas[0], idx),
-            C1::data(datas[1], idx),
-            C2::data(datas[2], idx),
-        )
-    }
+impl<C0> ComponentAccessesReadOnly for C0 where C0: ComponentAccessReadOnly {}
+
+//  Elements can be tuples themselves, so `datas` is split by each element's `COUNT`.
+macro_rules! impl_component_accesses_optional {
+    ($($c:ident),*) => {
+        impl<$($c),*> ComponentAccessesOptional for ($($c,)*)
+        where
+            $($c: ComponentAccessesOptional),*
+        {
+            const COUNT: usize = 0 $(+ $c::COUNT)*;
+
+            type Item<'a> = ($($c::Item<'a>,)*);
+
+            fn fetches() -> Vec<QueryFetch> {
+                let mut fetches = Vec::with_capacity(Self::COUNT);
+                $(fetches.append(&mut $c::fetches());)*
+                fetches
+            }
+
+            #[allow(unused_assignments)]
+            fn datas(datas: &[Option<*const u8>], idx: usize) -> Self {
+                let mut offset = 0;
+                ($({
+                    let data = $c::datas(&datas[offset..], idx);
+                    offset += $c::COUNT;
+                    data
+                },)*)
+            }
+
+            fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
+                $($c::component_maybe_insert(ctyp);)*
+            }
+
+            fn any_ofs() -> Vec<Vec<ComponentTypeId>> {
+                let mut any_ofs = Vec::new();
+                $(any_ofs.append(&mut $c::any_ofs());)*
+                any_ofs
+            }
+        }
 
-    fn component_maybe_insert(ctyp: &RwLock<ComponentTypePlanet>) {
-        C0::component_maybe_insert(ctyp);
-        C1::component_maybe_insert(ctyp);
-        C2::component_maybe_insert(ctyp);
-    }
+        impl<$($c),*> ComponentAccessesReadOnly for ($($c,)*)
+        where
+            $($c: ComponentAccessesReadOnly),*
+        {}
+    };
 }
+
+impl_component_accesses_optional!(C0);
+impl_component_accesses_optional!(C0, C1);
+impl_component_accesses_optional!(C0, C1, C2);
+impl_component_accesses_optional!(C0, C1, C2, C3);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5, C6);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5, C6, C7);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5, C6, C7, C8);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10);
+impl_component_accesses_optional!(C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11);
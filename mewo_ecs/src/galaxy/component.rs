@@ -1,4 +1,4 @@
-use super::ComponentTypeId;
+use super::{access::component_maybe_insert, ComponentStorage, ComponentTypeId, Galaxy};
 use crate::data::{data_clone, data_drop, hash_type, TypeEntry, ValueDrop, ValueDuplicate};
 
 pub trait CheapComponent: Copy {
@@ -54,4 +54,20 @@ pub trait GenericComponent {
     }
 
     fn mewo_component_duplicate() -> ValueDuplicate;
+
+    /// `CheapComponent`s are double buffered unless this says otherwise.
+    fn mewo_component_storage() -> ComponentStorage {
+        match Self::mewo_component_duplicate() {
+            ValueDuplicate::Copy => ComponentStorage::DoubleBuffered,
+            _ => ComponentStorage::Direct,
+        }
+    }
+}
+
+impl Galaxy {
+    /// Components are registered on first use, but this can be done ahead of time.
+    pub fn register_component<C: GenericComponent + 'static>(&self) -> &Self {
+        component_maybe_insert::<C>(&self.ctyp);
+        self
+    }
 }
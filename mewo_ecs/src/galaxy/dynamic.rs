@@ -0,0 +1,150 @@
+use super::{
+    ComponentTypeId, Entity, Galaxy, QueryAccess, QueryAccessType, QueryFetch, QueryFilterType,
+    QueryIter,
+};
+use crate::{data::TypeEntry, ecs::Result};
+
+/// A query built from [`ComponentTypeId`]s at runtime instead of types.
+/// Components must already be known to the galaxy, see [`Galaxy::get_component_type_id`].
+pub struct DynamicQueryInfo<'gal> {
+    incomplete: QueryAccess,
+    galaxy: &'gal Galaxy,
+    last_run: u64,
+    this_run: u64,
+}
+
+impl<'gal> DynamicQueryInfo<'gal> {
+    pub fn read(self, cty: ComponentTypeId) -> Self {
+        self.access(cty, QueryAccessType::Read)
+    }
+
+    pub fn write(self, cty: ComponentTypeId) -> Self {
+        self.access(cty, QueryAccessType::Write)
+    }
+
+    pub fn option_read(self, cty: ComponentTypeId) -> Self {
+        self.access(cty, QueryAccessType::OptionRead)
+    }
+
+    pub fn option_write(self, cty: ComponentTypeId) -> Self {
+        self.access(cty, QueryAccessType::OptionWrite)
+    }
+
+    pub fn with(self, cty: ComponentTypeId) -> Self {
+        self.filter(cty, QueryFilterType::With)
+    }
+
+    pub fn without(self, cty: ComponentTypeId) -> Self {
+        self.filter(cty, QueryFilterType::Without)
+    }
+
+    pub fn changed(self, cty: ComponentTypeId) -> Self {
+        self.filter(cty, QueryFilterType::Changed)
+    }
+
+    pub fn added(self, cty: ComponentTypeId) -> Self {
+        self.filter(cty, QueryFilterType::Added)
+    }
+
+    fn access(mut self, cty: ComponentTypeId, access: QueryAccessType) -> Self {
+        self.incomplete.accesses.push((cty, access));
+        self
+    }
+
+    fn filter(mut self, cty: ComponentTypeId, filter: QueryFilterType) -> Self {
+        self.incomplete.filters.push((cty, filter));
+        self
+    }
+
+    /// Fails if any accessed component isn't known to the galaxy.
+    pub fn iter(self) -> Result<DynamicQueryIter<'gal>> {
+        let types = {
+            let ctyp = self.galaxy.ctyp.read();
+            self.incomplete
+                .accesses
+                .iter()
+                .map(|&(cty, _)| ctyp.get_type(cty).cloned())
+                .collect::<Result<Vec<_>>>()?
+        };
+        let fetches = self
+            .incomplete
+            .accesses
+            .iter()
+            .map(|&(cty, access)| QueryFetch::Access(cty, access))
+            .collect();
+        Ok(DynamicQueryIter {
+            qiter: QueryIter::new(
+                self.galaxy,
+                self.incomplete,
+                fetches,
+                self.last_run,
+                self.this_run,
+            ),
+            types,
+        })
+    }
+}
+
+/// A matching entity from a [`DynamicQueryIter`].
+pub struct DynamicRow {
+    pub entity: Entity,
+    /// One for each access, in order.
+    /// These point straight at the component, or are `None` for missing optional components.
+    /// Only write through those accessed with `write` or `option_write`.
+    pub datas: Vec<Option<*const u8>>,
+}
+
+pub struct DynamicQueryIter<'gal> {
+    qiter: QueryIter<'gal, ()>,
+    types: Vec<TypeEntry>,
+}
+
+impl<'gal> DynamicQueryIter<'gal> {
+    /// One for each access, in order.
+    pub fn get_types(&self) -> &[TypeEntry] {
+        &self.types
+    }
+}
+
+impl<'gal> Iterator for DynamicQueryIter<'gal> {
+    type Item = DynamicRow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.qiter.next_idx()?;
+        Some(DynamicRow {
+            entity: self.qiter.get_current_entity(),
+            datas: self
+                .qiter
+                .get_current_datas()
+                .iter()
+                .zip(self.types.iter())
+                .map(|(data, ty)| data.map(|data| unsafe { data.add(idx * ty.size) }))
+                .collect(),
+        })
+    }
+}
+
+impl Galaxy {
+    /// See [`DynamicQueryInfo`].
+    pub fn dynamic_query(&self) -> DynamicQueryInfo<'_> {
+        DynamicQueryInfo {
+            incomplete: QueryAccess {
+                accesses: Vec::new(),
+                filters: Vec::new(),
+            },
+            galaxy: self,
+            last_run: self.last_update_tick(),
+            this_run: self.next_change_tick(),
+        }
+    }
+
+    /// Components are known once they've been inserted or queried.
+    /// Names come from `std::any::type_name`.
+    pub fn get_component_type_id(&self, name: &str) -> Option<ComponentTypeId> {
+        self.ctyp.read().get_type_id(name)
+    }
+
+    pub fn get_component_type(&self, cty: ComponentTypeId) -> Option<TypeEntry> {
+        self.ctyp.read().get_type(cty).ok().cloned()
+    }
+}
@@ -1,5 +1,6 @@
 use super::{
-    ComponentAccessesOptional, ComponentGroupId, Entity, Galaxy, GenericComponent, QueryAccessType,
+    access::component_maybe_insert, ComponentAccessesOptional, ComponentGroupId, ComponentTypeId,
+    Entity, Galaxy, GenericComponent, QueryAccessType, QueryFetch, QueryLockType,
     StorageModifyTransform, StorageTransform,
 };
 use crate::data::TVal;
@@ -53,12 +54,7 @@ impl<'gal, T> EntityGetter<'gal, T> {
     }
 
     fn component_maybe_insert<C: GenericComponent + 'static>(&self) {
-        let id = C::mewo_component_id();
-        if self.galaxy.ctyp.read().get_type(id).is_err() {
-            let mut ctyp = self.galaxy.ctyp.write();
-            ctyp.insert_type(id, C::mewo_component_type_entry())
-                .unwrap();
-        }
+        component_maybe_insert::<C>(&self.galaxy.ctyp)
     }
 }
 
@@ -92,6 +88,8 @@ pub struct EntityComponentGetter<'gal, CA: ComponentAccessesOptional> {
     group_id: ComponentGroupId,
     entity_idx: usize,
     datas: Vec<Option<*const u8>>,
+    //  One per component, see `access_filter`.
+    locks: Vec<(ComponentTypeId, QueryLockType)>,
     phantom: PhantomData<CA>,
 }
 
@@ -105,29 +103,66 @@ where
         let cgp = galaxy.cgp.read();
         let gid = sp.get_entity_group(entity).unwrap();
         let group = cgp.get_group(gid).unwrap();
-        let query = CA::infos();
+        let query = CA::fetches();
+        let entity_idx = sp.get_entity_idx(gid, entity).unwrap();
         let mut datas: Vec<Option<*const u8>> = query.iter().map(|_| None).collect();
+        for (idx, &fetch) in query.iter().enumerate() {
+            match fetch {
+                QueryFetch::Has(cty) => {
+                    *datas.get_mut(idx).unwrap() = group.has(cty).then_some(std::ptr::null());
+                }
+                QueryFetch::Entity => {
+                    *datas.get_mut(idx).unwrap() = sp.get_entities(gid).map(|ptr| ptr as *const u8);
+                }
+                QueryFetch::Access(..) => {}
+            }
+        }
+        //  The same component can be accessed more than once, such as with `Prev` and `Live`, so
+        //  each is locked once with the strongest lock.
+        let mut locks = Vec::new();
+        for &cty in group.get_components() {
+            let mut lock = None;
+            for &fetch in query.iter() {
+                if let QueryFetch::Access(qcty, qlock) = fetch {
+                    if qcty == cty {
+                        let lock = lock.get_or_insert(QueryLockType::Read);
+                        if let QueryLockType::Write = qlock.into_lock() {
+                            *lock = QueryLockType::Write;
+                        }
+                    }
+                }
+            }
+            if let Some(lock) = lock {
+                galaxy.lock_column(gid, cty, lock);
+                locks.push((cty, lock));
+            }
+        }
         for &cty in group.get_components() {
-            for (idx, &(qcty, qlock)) in query.iter().enumerate() {
-                if qcty == cty {
-                    match qlock {
+            for (idx, &fetch) in query.iter().enumerate() {
+                match fetch {
+                    QueryFetch::Access(qcty, qlock) if qcty == cty => match qlock {
                         QueryAccessType::Read | QueryAccessType::OptionRead => {
-                            sp.get_read_lock(gid, cty).unwrap();
                             *datas.get_mut(idx).unwrap() = sp.get_read(gid, cty);
                         }
+                        QueryAccessType::LiveRead => {
+                            *datas.get_mut(idx).unwrap() = sp.get_write(gid, cty);
+                        }
                         QueryAccessType::Write | QueryAccessType::OptionWrite => {
-                            sp.get_write_lock(gid, cty).unwrap();
-                            *datas.get_mut(idx).unwrap() = sp.get_read(gid, cty);
+                            *datas.get_mut(idx).unwrap() = sp.get_write(gid, cty);
+                            let ticks = sp.get_ticks(gid, cty, true).unwrap();
+                            unsafe { &*ticks }.set_changed(entity_idx, galaxy.next_change_tick());
                         }
-                    };
+                    },
+                    _ => {}
                 }
             }
         }
         EntityComponentGetter {
             galaxy,
-            entity_idx: sp.get_entity_idx(gid, entity).unwrap(),
+            entity_idx,
             group_id: gid,
             datas: datas.into_iter().collect(),
+            locks,
             phantom: PhantomData,
         }
     }
@@ -142,26 +177,9 @@ where
     CA: ComponentAccessesOptional,
 {
     fn drop(&mut self) {
-        let sp = self.galaxy.sp.read();
-        let cgp = self.galaxy.cgp.read();
-        let group = cgp.get_group(self.group_id).unwrap();
-        let query = CA::infos();
-
         //  Maybe it's safer to drop locks in order?
-        for &cty in group.get_components() {
-            for &(qcty, qlock) in query.iter() {
-                if qcty == cty {
-                    match qlock {
-                        //  Some(whatever.unwrap()) is intentional.
-                        QueryAccessType::Read | QueryAccessType::OptionRead => {
-                            sp.get_read_unlock(self.group_id, cty).unwrap()
-                        }
-                        QueryAccessType::Write | QueryAccessType::OptionWrite => {
-                            sp.get_write_unlock(self.group_id, cty).unwrap()
-                        }
-                    };
-                }
-            }
+        for &(cty, lock) in self.locks.iter() {
+            self.galaxy.unlock_column(self.group_id, cty, lock);
         }
     }
 }
@@ -63,6 +63,12 @@ impl Galaxy {
         unsafe { std::slice::from_raw_parts(events.ptr() as *const E, events.len()) }
     }
 
+    /// Events are registered on first use, but this can be done ahead of time.
+    pub fn register_event<E: Event + 'static>(&self) -> &Self {
+        self.event_maybe_insert::<E>();
+        self
+    }
+
     fn event_maybe_insert<E: Event + 'static>(&self) {
         let id = E::mewo_event_id();
         if self.evp.read().get_type(id).is_none() {
@@ -0,0 +1,55 @@
+use super::Galaxy;
+use std::sync::atomic::Ordering;
+
+/// Why a galaxy stopped, returned by the runners.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Exit {
+    #[default]
+    Success,
+    Failure {
+        code: i32,
+        message: String,
+    },
+}
+
+impl Exit {
+    pub fn failure<S: Into<String>>(code: i32, message: S) -> Self {
+        Exit::Failure {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, Exit::Success)
+    }
+
+    /// `0` on success, like a process exit code.
+    pub fn get_code(&self) -> i32 {
+        match self {
+            Exit::Success => 0,
+            Exit::Failure { code, .. } => *code,
+        }
+    }
+}
+
+impl Galaxy {
+    /// Stop with [`Exit::Success`], see [`Galaxy::set_exit_with`].
+    pub fn set_exit(&self) {
+        self.set_exit_with(Exit::Success)
+    }
+
+    /// Stop after this frame, the next `update` returns `None`.
+    /// Only the first exit is kept.
+    pub fn set_exit_with(&self, exit: Exit) {
+        let mut current = self.exit.lock();
+        if current.is_none() {
+            *current = Some(exit);
+            self.exiting.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub fn get_exit(&self) -> Option<Exit> {
+        self.exit.lock().clone()
+    }
+}
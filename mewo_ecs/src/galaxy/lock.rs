@@ -0,0 +1,140 @@
+use super::{ComponentGroupId, ComponentTypeId, Galaxy, QueryLockType};
+use std::{cell::RefCell, collections::HashMap, sync::atomic::Ordering};
+
+//  Only tracked with debug locks on.
+#[derive(Default)]
+struct HeldColumn {
+    reads: usize,
+    writes: usize,
+}
+
+thread_local! {
+    //  Columns locked by this thread, keyed by galaxy as well.
+    static HELD: RefCell<HashMap<(usize, ComponentGroupId, ComponentTypeId), HeldColumn>> =
+        RefCell::new(HashMap::new());
+}
+
+impl Galaxy {
+    /// Panic instead of deadlocking when a thread locks components that it already holds, such
+    /// as by iterating over `&mut C` while iterating over `&C`.
+    /// This costs a little on every lock, so it's off by default.
+    pub fn set_debug_locks(&self, enabled: bool) {
+        self.debug_locks.store(enabled, Ordering::SeqCst);
+    }
+
+    pub(super) fn lock_column(
+        &self,
+        gid: ComponentGroupId,
+        cty: ComponentTypeId,
+        lock: QueryLockType,
+    ) {
+        if self.debug_locks.load(Ordering::SeqCst) {
+            if self.try_lock_column(gid, cty, lock) {
+                return;
+            }
+            //  Someone else may have it, but not us.
+            let reentrant = HELD.with(|held| {
+                held.borrow()
+                    .get(&self.held_key(gid, cty))
+                    .map(|held| {
+                        held.writes > 0 || (held.reads > 0 && matches!(lock, QueryLockType::Write))
+                    })
+                    .unwrap_or(false)
+            });
+            if reentrant {
+                panic!(
+                    "`{}` is already locked by this thread, locking it again would deadlock. Drop the other query first or use `try_iter`.",
+                    self.get_component_name(cty)
+                );
+            }
+        }
+        //  Callers may already be reading the storage planet.
+        let sp = self.sp.read_recursive();
+        match lock {
+            QueryLockType::Read => sp.get_read_lock(gid, cty),
+            QueryLockType::Write => sp.get_write_lock(gid, cty),
+        }
+        .unwrap();
+        self.track_column(gid, cty, lock, true);
+    }
+
+    pub(super) fn try_lock_column(
+        &self,
+        gid: ComponentGroupId,
+        cty: ComponentTypeId,
+        lock: QueryLockType,
+    ) -> bool {
+        let sp = self.sp.read_recursive();
+        let locked = match lock {
+            QueryLockType::Read => sp.get_try_read_lock(gid, cty),
+            QueryLockType::Write => sp.get_try_write_lock(gid, cty),
+        }
+        .unwrap()
+        .unwrap();
+        if locked {
+            self.track_column(gid, cty, lock, true);
+        }
+        locked
+    }
+
+    pub(super) fn unlock_column(
+        &self,
+        gid: ComponentGroupId,
+        cty: ComponentTypeId,
+        lock: QueryLockType,
+    ) {
+        let sp = self.sp.read_recursive();
+        match lock {
+            QueryLockType::Read => sp.get_read_unlock(gid, cty),
+            QueryLockType::Write => sp.get_write_unlock(gid, cty),
+        }
+        .unwrap();
+        self.track_column(gid, cty, lock, false);
+    }
+
+    pub(super) fn get_component_name(&self, cty: ComponentTypeId) -> String {
+        self.ctyp
+            .read()
+            .get_type(cty)
+            .map(|ty| ty.name.clone())
+            .unwrap_or_else(|_| format!("{:?}", cty))
+    }
+
+    fn held_key(
+        &self,
+        gid: ComponentGroupId,
+        cty: ComponentTypeId,
+    ) -> (usize, ComponentGroupId, ComponentTypeId) {
+        (self as *const Galaxy as usize, gid, cty)
+    }
+
+    fn track_column(
+        &self,
+        gid: ComponentGroupId,
+        cty: ComponentTypeId,
+        lock: QueryLockType,
+        locked: bool,
+    ) {
+        if !self.debug_locks.load(Ordering::SeqCst) {
+            return;
+        }
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            let key = self.held_key(gid, cty);
+            let column = held.entry(key).or_default();
+            let count = match lock {
+                QueryLockType::Read => &mut column.reads,
+                QueryLockType::Write => &mut column.writes,
+            };
+            //  Debug locks may have been turned on while this was held.
+            *count = if locked {
+                *count + 1
+            } else {
+                count.saturating_sub(1)
+            };
+            if column.reads == 0 && column.writes == 0 {
+                held.remove(&key);
+            }
+        });
+    }
+}
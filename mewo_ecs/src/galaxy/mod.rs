@@ -1,29 +1,39 @@
 use super::{
     data::{ThreadLocal, ThreadLocalGuard},
     ecs::{
-        ComponentGroupId, ComponentGroupPlanet, ComponentTypeId, ComponentTypePlanet, Entity,
-        EntityPlanet, EventId, EventModify, EventPlanet, QueryAccess, QueryAccessType,
-        QueryFilterType, QueryId, QueryLockType, QueryPlanet, ResourceId, ResourcePlanet,
-        ResourceTypeId, StorageModifyTransform, StoragePlanet, StorageTransform,
+        ComponentGroupId, ComponentGroupPlanet, ComponentStorage, ComponentTypeId,
+        ComponentTypePlanet, Entity, EntityPlanet, EventId, EventModify, EventPlanet, QueryAccess,
+        QueryAccessType, QueryFilterType, QueryId, QueryLockType, QueryPlanet, ResourceId,
+        ResourcePlanet, ResourceTypeId, RowTicks, StorageModifyTransform, StoragePlanet,
+        StorageTransform,
     },
 };
-use parking_lot::RwLock;
-use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::{Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 mod access;
 mod component;
+mod dynamic;
 mod entity;
 mod event;
+mod exit;
+mod lock;
 mod query;
 mod resource;
 
 #[cfg(test)]
 mod test;
 
-pub use access::{ComponentAccessOptional, ComponentAccessesNormal, ComponentAccessesOptional};
+pub use access::{
+    AnyOf, ComponentAccessAnyOf, ComponentAccessesAnyOf, ComponentAccessesNormal,
+    ComponentAccessesOptional, ComponentAccessesReadOnly, Has, Live, Prev, QueryFetch,
+};
 pub use component::{CheapComponent, Component, GenericComponent, UniqueComponent};
+pub use dynamic::{DynamicQueryInfo, DynamicQueryIter, DynamicRow};
 pub use entity::EntityGetter;
 pub use event::Event;
+pub use exit::Exit;
+pub use query::{QueryCombinations, QueryEIter, QueryGet, QueryInfo, QueryIter};
 pub use resource::{Resource, ResourceReadGuard, ResourceWriteGuard};
 
 pub struct Galaxy {
@@ -41,7 +51,14 @@ pub struct Galaxy {
     ev_modify: ThreadLocal<EventModify>,
     st_transforms: ThreadLocal<Vec<StorageTransform>>,
 
-    exit: AtomicBool,
+    exiting: AtomicBool,
+    exit: Mutex<Option<Exit>>,
+
+    //  Stamped onto components when they're inserted or accessed with `&mut`.
+    change_tick: AtomicU64,
+    update_tick: AtomicU64,
+
+    debug_locks: AtomicBool,
 }
 
 impl Galaxy {
@@ -61,19 +78,33 @@ impl Galaxy {
             ev_modify: ThreadLocal::new(),
             st_transforms: ThreadLocal::new(),
 
-            exit: AtomicBool::new(false),
+            exiting: AtomicBool::new(false),
+            exit: Mutex::new(None),
+
+            change_tick: AtomicU64::new(0),
+            update_tick: AtomicU64::new(0),
+
+            debug_locks: AtomicBool::new(false),
         }
     }
 
-    pub fn set_exit(&self) {
-        self.exit.store(true, Ordering::SeqCst);
+    pub fn update(&mut self) -> Option<()> {
+        self.update_with_events(true)
     }
 
-    pub fn update(&mut self) -> Option<()> {
-        if self.exit.load(Ordering::SeqCst) {
+    //  Fixed steps apply entity and component changes, but events are left for the next frame.
+    //  Otherwise, events sent during a frame would be cleared before the next frame's systems see
+    //  them.
+    pub(crate) fn update_fixed(&mut self) -> Option<()> {
+        self.update_with_events(false)
+    }
+
+    fn update_with_events(&mut self, events: bool) -> Option<()> {
+        if self.exiting.load(Ordering::SeqCst) {
             None?
         }
 
+        let tick = self.next_change_tick();
         let mut evp = self.evp.write();
 
         let mut ep = self.ep.write();
@@ -82,9 +113,11 @@ impl Galaxy {
         let mut sp = self.sp.write();
         let mut qp = self.qp.write();
 
-        let ev_modifies = unsafe { self.ev_modify.get_inner() };
-        for ev_modify in ev_modifies.iter_mut() {
-            evp.modify(ev_modify).unwrap();
+        if events {
+            let ev_modifies = unsafe { self.ev_modify.get_inner() };
+            for ev_modify in ev_modifies.iter_mut() {
+                evp.modify(ev_modify).unwrap();
+            }
         }
 
         let st_transforms = unsafe { self.st_transforms.get_inner() };
@@ -93,17 +126,28 @@ impl Galaxy {
                 //  Eh, it'll get cleared anyway.
                 let trans =
                     std::mem::replace(trans, StorageTransform::Remove(Entity::from(0, 888)));
-                sp.transform(&mut ep, &ctyp, &mut cgp, &mut qp, trans)
+                sp.transform(&mut ep, &ctyp, &mut cgp, &mut qp, trans, tick)
                     .unwrap();
             }
             st_trans.clear()
         }
 
-        sp.update();
+        sp.update(tick);
+        self.update_tick.store(tick, Ordering::SeqCst);
 
         Some(())
     }
 
+    //  The first tick is 1, so a system that has never run (0) sees everything as changed.
+    pub(crate) fn next_change_tick(&self) -> u64 {
+        self.change_tick.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    //  Ticks after this were stamped during or after the last update.
+    fn last_update_tick(&self) -> u64 {
+        self.update_tick.load(Ordering::SeqCst).saturating_sub(1)
+    }
+
     fn get_event_modify(&self) -> ThreadLocalGuard<EventModify> {
         self.ev_modify.get_or(EventModify::new)
     }
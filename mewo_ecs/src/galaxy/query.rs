@@ -1,20 +1,28 @@
 use super::{
-    ComponentAccessOptional, ComponentAccessesNormal, ComponentAccessesOptional, Entity, Galaxy,
-    QueryAccess, QueryFilterType, QueryId, QueryLockType,
+    ComponentAccessesNormal, ComponentAccessesOptional, ComponentAccessesReadOnly,
+    ComponentGroupId, ComponentTypeId, Entity, Galaxy, QueryAccess, QueryFetch, QueryFilterType,
+    QueryId, QueryLockType, RowTicks, StoragePlanet,
 };
-use std::marker::PhantomData;
+use crate::{
+    data::ThreadPool,
+    ecs::{ECSError, ErrorType, Result},
+    ecs_err,
+};
+use std::{collections::HashMap, marker::PhantomData};
 
 //  TODO OPT: Don't write lock storages if they are len == 0.
 
 pub struct QueryInfo<'gal, CA> {
     incomplete: QueryAccess,
     galaxy: &'gal Galaxy,
+    last_run: u64,
+    this_run: u64,
     phantom: PhantomData<CA>,
 }
 
 impl<'gal, CA> QueryInfo<'gal, CA>
 where
-    CA: ComponentAccessOptional,
+    CA: ComponentAccessesOptional,
 {
     pub fn with<CF: ComponentAccessesNormal>(mut self) -> Self {
         CF::component_maybe_insert(&self.galaxy.ctyp);
@@ -38,43 +46,173 @@ where
         self
     }
 
+    /// Only entities with at least one of `CF`.
+    pub fn or<CF: ComponentAccessesNormal>(mut self) -> Self {
+        CF::component_maybe_insert(&self.galaxy.ctyp);
+        let idx = self.next_or_idx();
+        self.incomplete.filters.append(
+            &mut CF::hashes()
+                .into_iter()
+                .map(|cty| (cty, QueryFilterType::Or(idx)))
+                .collect(),
+        );
+        self
+    }
+
+    /// Only entities whose `CF` were changed or inserted since this system last ran.
+    /// Outside of a [`crate::run::Query`], since the last [`Galaxy::update`].
+    /// A `&mut` access counts as a change, even when nothing was written.
+    pub fn changed<CF: ComponentAccessesNormal>(mut self) -> Self {
+        CF::component_maybe_insert(&self.galaxy.ctyp);
+        self.incomplete.filters.append(
+            &mut CF::hashes()
+                .into_iter()
+                .map(|cty| (cty, QueryFilterType::Changed))
+                .collect(),
+        );
+        self
+    }
+
+    /// Only entities whose `CF` were inserted since this system last ran, see
+    /// [`QueryInfo::changed`].
+    pub fn added<CF: ComponentAccessesNormal>(mut self) -> Self {
+        CF::component_maybe_insert(&self.galaxy.ctyp);
+        self.incomplete.filters.append(
+            &mut CF::hashes()
+                .into_iter()
+                .map(|cty| (cty, QueryFilterType::Added))
+                .collect(),
+        );
+        self
+    }
+
     pub fn iter(self) -> QueryIter<'gal, CA> {
-        let Self {
-            incomplete,
-            galaxy,
-            phantom,
-        } = self;
-        let _ = phantom;
-        QueryIter {
-            galaxy,
-            qid: {
-                let maybe_qid = galaxy.qp.read().get_query_id(&incomplete);
-                if let Some(qid) = maybe_qid {
-                    qid
-                } else {
-                    let mut qp = self.galaxy.qp.write();
-                    qp.insert_access(
-                        &self.galaxy.ctyp,
-                        &self.galaxy.cgp,
-                        &self.galaxy.sp,
-                        incomplete,
-                    )
-                    .unwrap()
-                }
-            },
-            current_storage: None,
-            current_datas: None,
-            current_entities: None,
-            current_storage_len: None,
-            group_idx: 0,
-            storage_idx: 0,
-            phantom: PhantomData,
-        }
+        QueryIter::new(
+            self.galaxy,
+            self.incomplete,
+            CA::fetches(),
+            self.last_run,
+            self.this_run,
+        )
     }
 
     pub fn eiter(self) -> QueryEIter<'gal, CA> {
         QueryEIter { qiter: self.iter() }
     }
+
+    /// Look up `entity` without iterating.
+    /// `None` if `entity` doesn't exist yet or doesn't match the query, including its filters.
+    /// Columns stay locked until the returned [`QueryGet`] is dropped, so don't hold on to it
+    /// while getting another entity from the same storage.
+    pub fn get(self, entity: Entity) -> Option<QueryGet<'gal, CA>> {
+        self.iter().into_get(entity, false).unwrap()
+    }
+
+    /// Like [`QueryInfo::iter`], but fails with [`ErrorType::QueryLockConflict`] instead of
+    /// blocking when any of the components are already locked.
+    /// Every group is locked upfront rather than as it's reached.
+    pub fn try_iter(self) -> Result<QueryIter<'gal, CA>> {
+        let mut qiter = self.iter();
+        let group_count = qiter
+            .galaxy
+            .qp
+            .read()
+            .get_access(qiter.qid)
+            .unwrap()
+            .groups
+            .len();
+        for group_idx in 0..group_count {
+            qiter.try_lock_group(group_idx)?;
+        }
+        Ok(qiter)
+    }
+
+    /// See [`QueryInfo::get`] and [`QueryInfo::try_iter`].
+    pub fn try_get(self, entity: Entity) -> Result<Option<QueryGet<'gal, CA>>> {
+        self.iter().into_get(entity, true)
+    }
+
+    /// Every unordered set of `K` distinct matches, each yielded once.
+    /// All matching groups stay locked until the iterator is dropped.
+    /// Items can be kept around, so `CA` can't have `&mut` accesses; use
+    /// [`QueryInfo::for_each_combination`] instead.
+    pub fn iter_combinations<const K: usize>(self) -> QueryCombinations<'gal, CA, K>
+    where
+        CA: ComponentAccessesReadOnly,
+    {
+        self.iter().into_combinations()
+    }
+
+    /// Call `f` with every unordered set of `K` distinct matches.
+    /// Unlike [`QueryInfo::iter_combinations`], `CA` can have `&mut` accesses.
+    /// Items are only borrowed for the call, so the same `&mut C` can't be kept from two
+    /// combinations.
+    pub fn for_each_combination<const K: usize, F>(self, mut f: F)
+    where
+        F: for<'a> FnMut([CA::Item<'a>; K]),
+    {
+        let mut combinations = self.iter().into_combinations::<K>();
+        while let Some(combination) = combinations.fetch_next(|group, idx| group.fetch_item(idx)) {
+            f(combination)
+        }
+    }
+
+    /// The number of matches.
+    /// Only lengths are read unless there are `changed` or `added` filters.
+    pub fn count(self) -> usize {
+        self.iter().count_matches()
+    }
+
+    //  `QueryInfo` is consumed like the rest of its builder.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn is_empty(self) -> bool {
+        let qiter = self.iter();
+        let mut empty = true;
+        qiter.for_each_match(|_, _| {
+            empty = false;
+            false
+        });
+        empty
+    }
+
+    /// The one and only match.
+    /// Fails with [`ErrorType::QuerySingle`] if there are none or more than one.
+    /// Columns stay locked until the returned [`QueryGet`] is dropped.
+    pub fn get_single(self) -> Result<QueryGet<'gal, CA>> {
+        self.iter().into_single()
+    }
+
+    /// See [`QueryInfo::get_single`], but panics instead.
+    pub fn single(self) -> QueryGet<'gal, CA> {
+        self.get_single().unwrap()
+    }
+
+    /// Call `f` on every match, split into batches of `batch_size` rows that run on the global
+    /// thread pool.
+    /// Each group of storages is locked the same way as [`QueryInfo::iter`], and this returns
+    /// once every batch has finished.
+    /// Use [`Entity`] in `CA` if you need it.
+    /// Matches are handed to other threads, so `CA` has to be `Send`, meaning that components
+    /// read with `&C` have to be `Sync` and ones written with `&mut C` have to be `Send`.
+    pub fn par_for_each<F>(self, batch_size: usize, f: F)
+    where
+        CA: Send,
+        F: Fn(CA) + Send + Sync,
+    {
+        self.iter().par_for_each(batch_size, f)
+    }
+
+    fn next_or_idx(&self) -> usize {
+        self.incomplete
+            .filters
+            .iter()
+            .filter_map(|(_, ctf)| match ctf {
+                QueryFilterType::Or(idx) => Some(idx + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 struct QueryStorageGuard<'gal> {
@@ -89,12 +227,7 @@ impl<'gal> QueryStorageGuard<'gal> {
         let access = qp.get_access(qid).unwrap();
         let (gid, ord, locks) = &access.groups[group_idx];
         for cty in ord.get_components().iter() {
-            let lock = locks.get(cty).unwrap();
-            match lock {
-                QueryLockType::Read => galaxy.sp.read().get_read_lock(*gid, *cty),
-                QueryLockType::Write => galaxy.sp.read().get_write_lock(*gid, *cty),
-            }
-            .unwrap();
+            galaxy.lock_column(*gid, *cty, *locks.get(cty).unwrap());
         }
         QueryStorageGuard {
             qid,
@@ -102,6 +235,39 @@ impl<'gal> QueryStorageGuard<'gal> {
             galaxy,
         }
     }
+
+    //  Nothing stays locked on failure.
+    pub fn try_new(qid: QueryId, group_idx: usize, galaxy: &'gal Galaxy) -> Result<Self> {
+        let qp = galaxy.qp.read();
+        let access = qp.get_access(qid).unwrap();
+        let (gid, ord, locks) = &access.groups[group_idx];
+        let mut locked = Vec::new();
+        let mut conflicts = Vec::new();
+        for &cty in ord.get_components().iter() {
+            let lock = *locks.get(&cty).unwrap();
+            if galaxy.try_lock_column(*gid, cty, lock) {
+                locked.push((cty, lock));
+            } else {
+                conflicts.push(galaxy.get_component_name(cty));
+            }
+        }
+        if !conflicts.is_empty() {
+            for (cty, lock) in locked {
+                galaxy.unlock_column(*gid, cty, lock);
+            }
+            Err(ecs_err!(
+                ErrorType::QueryLockConflict {
+                    components: conflicts
+                },
+                gid
+            ))?
+        }
+        Ok(QueryStorageGuard {
+            qid,
+            group_idx,
+            galaxy,
+        })
+    }
 }
 
 impl<'gal> Drop for QueryStorageGuard<'gal> {
@@ -111,11 +277,7 @@ impl<'gal> Drop for QueryStorageGuard<'gal> {
         //  Order doesn't matter does it?
         let (gid, _, locks) = &access.groups[self.group_idx];
         for (cty, lock) in locks {
-            match lock {
-                QueryLockType::Read => self.galaxy.sp.read().get_read_unlock(*gid, *cty),
-                QueryLockType::Write => self.galaxy.sp.read().get_write_unlock(*gid, *cty),
-            }
-            .unwrap();
+            self.galaxy.unlock_column(*gid, *cty, *lock);
         }
     }
 }
@@ -124,28 +286,106 @@ pub struct QueryIter<'gal, CA> {
     galaxy: &'gal Galaxy,
     qid: QueryId,
     current_storage: Option<QueryStorageGuard<'gal>>,
+    //  Groups locked ahead of time, see `QueryInfo::try_iter`.
+    locked: Vec<Option<QueryStorageGuard<'gal>>>,
     current_datas: Option<Vec<Option<*const u8>>>,
     current_entities: Option<*const Entity>,
     current_storage_len: Option<usize>,
+    //  Follows `tick_filters`.
+    current_filter_ticks: Option<Vec<*const RowTicks>>,
+    current_write_ticks: Option<Vec<*const RowTicks>>,
+    fetches: Vec<QueryFetch>,
+    tick_filters: Vec<(ComponentTypeId, QueryFilterType)>,
+    last_run: u64,
+    this_run: u64,
     group_idx: usize,
     storage_idx: usize,
     phantom: PhantomData<CA>,
 }
 
 impl<'gal, CA> QueryIter<'gal, CA> {
+    //  `fetches` decides what goes in `current_datas`, see `ComponentAccessesOptional::fetches`.
+    pub(super) fn new(
+        galaxy: &'gal Galaxy,
+        incomplete: QueryAccess,
+        fetches: Vec<QueryFetch>,
+        last_run: u64,
+        this_run: u64,
+    ) -> Self {
+        let tick_filters = incomplete
+            .filters
+            .iter()
+            .filter(|(_, ctf)| matches!(ctf, QueryFilterType::Changed | QueryFilterType::Added))
+            .copied()
+            .collect();
+        QueryIter {
+            galaxy,
+            qid: {
+                let maybe_qid = galaxy.qp.read().get_query_id(&incomplete);
+                if let Some(qid) = maybe_qid {
+                    qid
+                } else {
+                    let mut qp = galaxy.qp.write();
+                    qp.insert_access(&galaxy.ctyp, &galaxy.cgp, &galaxy.sp, incomplete)
+                        .unwrap()
+                }
+            },
+            current_storage: None,
+            locked: Vec::new(),
+            current_datas: None,
+            current_entities: None,
+            current_storage_len: None,
+            current_filter_ticks: None,
+            current_write_ticks: None,
+            fetches,
+            tick_filters,
+            last_run,
+            this_run,
+            group_idx: 0,
+            storage_idx: 0,
+            phantom: PhantomData,
+        }
+    }
+
     //  Call after next.
     pub fn get_current_entity(&self) -> Entity {
         unsafe { *self.current_entities.unwrap().add(self.storage_idx - 1) }
     }
-}
 
-impl<'gal, CA> Iterator for QueryIter<'gal, CA>
-where
-    CA: ComponentAccessesOptional,
-{
-    type Item = CA;
+    fn passes_tick_filters(&self, idx: usize) -> bool {
+        self.passes_tick_filters_with(self.current_filter_ticks.as_ref().unwrap(), idx)
+    }
+
+    fn passes_tick_filters_with(&self, filter_ticks: &[*const RowTicks], idx: usize) -> bool {
+        self.tick_filters
+            .iter()
+            .zip(filter_ticks)
+            .all(|(&(_, ctf), &ticks)| {
+                let ticks = unsafe { &*ticks }.get(idx);
+                match ctf {
+                    QueryFilterType::Changed => ticks.changed > self.last_run,
+                    QueryFilterType::Added => ticks.added > self.last_run,
+                    _ => unreachable!(),
+                }
+            })
+    }
+
+    fn unload_group(&mut self) {
+        self.group_idx += 1;
+        self.current_storage = None;
+        self.current_datas = None;
+        self.current_storage_len = None;
+        self.current_filter_ticks = None;
+        self.current_write_ticks = None;
+    }
+
+    //  Lock the current group if it isn't already.
+    //  Returns `None` once every group has been visited.
+    fn load_group(&mut self) -> Option<()> {
+        if self.current_storage.is_some() {
+            return Some(());
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
         let qp = self.galaxy.qp.read();
         let access = qp.get_access(self.qid).unwrap();
 
@@ -153,42 +393,402 @@ where
             None?
         }
 
-        let (gid, ord, locks) = &access.groups[self.group_idx];
+        let (gid, _, locks) = &access.groups[self.group_idx];
 
-        if self.current_storage.is_none() {
-            self.current_storage_len = Some(self.galaxy.sp.read().get_len(*gid));
-            self.current_storage = Some(QueryStorageGuard::new(
-                self.qid,
-                self.group_idx,
-                self.galaxy,
-            ));
-            let sp = self.galaxy.sp.read();
-            self.current_datas = Some(
-                ord.get_components()
-                    .iter()
-                    .map(|cid| {
-                        let lock = locks.get(cid).unwrap();
-                        match lock {
-                            QueryLockType::Read => sp.get_read(*gid, *cid),
-                            QueryLockType::Write => sp.get_write(*gid, *cid),
+        self.current_storage_len = Some(self.galaxy.sp.read().get_len(*gid));
+        self.current_storage = Some(
+            self.locked
+                .get_mut(self.group_idx)
+                .and_then(Option::take)
+                .unwrap_or_else(|| QueryStorageGuard::new(self.qid, self.group_idx, self.galaxy)),
+        );
+        let sp = self.galaxy.sp.read();
+        let cgp = self.galaxy.cgp.read();
+        let group = cgp.get_group(*gid).unwrap();
+        //  `datas` must follow the order of `fetches`, not the group.
+        //  Optional components missing from this group are `None`.
+        self.current_datas = Some(
+            self.fetches
+                .iter()
+                .map(|fetch| match fetch {
+                    QueryFetch::Access(cid, access) => {
+                        locks.get(cid)?;
+                        if access.is_live() {
+                            sp.get_write(*gid, *cid)
+                        } else {
+                            sp.get_read(*gid, *cid)
                         }
-                    })
-                    .collect(),
+                    }
+                    QueryFetch::Has(cid) => group.has(*cid).then_some(std::ptr::null()),
+                    QueryFetch::Entity => sp.get_entities(*gid).map(|ptr| ptr as *const u8),
+                })
+                .collect(),
+        );
+        self.current_filter_ticks = Some(self.filter_ticks(&sp, *gid, locks));
+        //  `Live` reads take a write lock too, but don't count as changes.
+        let mut written = self
+            .fetches
+            .iter()
+            .filter_map(|fetch| match fetch {
+                QueryFetch::Access(cid, access) if access.is_write() && locks.contains_key(cid) => {
+                    Some(*cid)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        written.sort();
+        written.dedup();
+        self.current_write_ticks = Some(
+            written
+                .into_iter()
+                .map(|cid| sp.get_ticks(*gid, cid, true).unwrap())
+                .collect(),
+        );
+        self.current_entities = Some(sp.get_entities(*gid).unwrap());
+        self.storage_idx = 0;
+        Some(())
+    }
+
+    //  Copycat components have separate ticks for reading and writing.
+    fn filter_ticks(
+        &self,
+        sp: &StoragePlanet,
+        gid: ComponentGroupId,
+        locks: &HashMap<ComponentTypeId, QueryLockType>,
+    ) -> Vec<*const RowTicks> {
+        self.tick_filters
+            .iter()
+            .map(|(cid, _)| {
+                let write = matches!(locks.get(cid), Some(QueryLockType::Write));
+                sp.get_ticks(gid, *cid, write).unwrap()
+            })
+            .collect()
+    }
+
+    //  Call `f` with the group index and row of each match until it returns `false`.
+    //  Ticks are atomic, so nothing needs to be locked.
+    fn for_each_match(&self, mut f: impl FnMut(usize, usize) -> bool) {
+        let qp = self.galaxy.qp.read();
+        let sp = self.galaxy.sp.read();
+        for (group_idx, (gid, _, locks)) in
+            qp.get_access(self.qid).unwrap().groups.iter().enumerate()
+        {
+            let filter_ticks = self.filter_ticks(&sp, *gid, locks);
+            for idx in 0..sp.get_len(*gid) {
+                if self.passes_tick_filters_with(&filter_ticks, idx) && !f(group_idx, idx) {
+                    return;
+                }
+            }
+        }
+    }
+
+    //  See `QueryInfo::count`.
+    fn count_matches(&self) -> usize {
+        if self.tick_filters.is_empty() {
+            let qp = self.galaxy.qp.read();
+            let sp = self.galaxy.sp.read();
+            return qp
+                .get_access(self.qid)
+                .unwrap()
+                .groups
+                .iter()
+                .map(|(gid, _, _)| sp.get_len(*gid))
+                .sum();
+        }
+        let mut count = 0;
+        self.for_each_match(|_, _| {
+            count += 1;
+            true
+        });
+        count
+    }
+
+    //  See `QueryInfo::get_single`.
+    fn into_single(mut self) -> Result<QueryGet<'gal, CA>> {
+        let mut found = Vec::with_capacity(2);
+        self.for_each_match(|group_idx, idx| {
+            found.push((group_idx, idx));
+            found.len() < 2
+        });
+        match found[..] {
+            [(group_idx, idx)] => {
+                self.group_idx = group_idx;
+                self.load_group().unwrap();
+                //  Another system may have written to it since scanning.
+                if self.passes_tick_filters(idx) {
+                    return Ok(QueryGet { qiter: self, idx });
+                }
+                self.unload_group();
+                Err(ecs_err!(ErrorType::QuerySingle { count: 0 }, self.qid))
+            }
+            _ => Err(ecs_err!(
+                ErrorType::QuerySingle {
+                    count: self.count_matches()
+                },
+                self.qid
+            )),
+        }
+    }
+
+    //  See `QueryInfo::get`.
+    fn into_get(mut self, entity: Entity, try_lock: bool) -> Result<Option<QueryGet<'gal, CA>>> {
+        let galaxy = self.galaxy;
+        let Some(gid) = galaxy.sp.read().get_entity_group(entity) else {
+            return Ok(None);
+        };
+        let Some(group_idx) = galaxy
+            .qp
+            .read()
+            .get_access(self.qid)
+            .unwrap()
+            .groups
+            .iter()
+            .position(|(group_gid, _, _)| *group_gid == gid)
+        else {
+            return Ok(None);
+        };
+        self.group_idx = group_idx;
+        if try_lock {
+            self.try_lock_group(group_idx)?;
+        }
+        self.load_group().unwrap();
+        Ok(galaxy
+            .sp
+            .read()
+            .get_entity_idx(gid, entity)
+            .filter(|&idx| idx < self.current_storage_len.unwrap() && self.passes_tick_filters(idx))
+            .map(|idx| QueryGet { qiter: self, idx }))
+    }
+
+    //  Lock a group ahead of time, failing instead of blocking.
+    fn try_lock_group(&mut self, group_idx: usize) -> Result<()> {
+        if self.locked.len() <= group_idx {
+            self.locked.resize_with(group_idx + 1, || None);
+        }
+        self.locked[group_idx] = Some(QueryStorageGuard::try_new(
+            self.qid,
+            group_idx,
+            self.galaxy,
+        )?);
+        Ok(())
+    }
+
+    //  An unloaded copy of this iterator starting at `group_idx`.
+    fn with_group(&self, group_idx: usize) -> Self {
+        QueryIter {
+            galaxy: self.galaxy,
+            qid: self.qid,
+            current_storage: None,
+            locked: Vec::new(),
+            current_datas: None,
+            current_entities: None,
+            current_storage_len: None,
+            current_filter_ticks: None,
+            current_write_ticks: None,
+            fetches: self.fetches.clone(),
+            tick_filters: self.tick_filters.clone(),
+            last_run: self.last_run,
+            this_run: self.this_run,
+            group_idx,
+            storage_idx: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    //  See `QueryInfo::iter_combinations`.
+    //  Combinations can span groups, so every group is locked at once.
+    //  Each group has its own storages, so this can't lock anything twice.
+    fn into_combinations<const K: usize>(self) -> QueryCombinations<'gal, CA, K> {
+        let group_count = self
+            .galaxy
+            .qp
+            .read()
+            .get_access(self.qid)
+            .unwrap()
+            .groups
+            .len();
+        let mut groups = Vec::with_capacity(group_count);
+        let mut rows = Vec::new();
+        for group_idx in 0..group_count {
+            let mut group = self.with_group(group_idx);
+            group.load_group().unwrap();
+            rows.extend(
+                (0..group.current_storage_len.unwrap())
+                    .filter(|&idx| group.passes_tick_filters(idx))
+                    .map(|idx| (group_idx, idx)),
             );
-            self.current_entities = Some(sp.get_entities(*gid).unwrap());
-            self.storage_idx = 0;
+            groups.push(group);
+        }
+        QueryCombinations {
+            done: rows.len() < K,
+            groups,
+            rows,
+            indices: std::array::from_fn(|i| i),
+        }
+    }
+
+    //  Whether row `idx` of the current group passes the tick filters, marking it as changed if
+    //  it's written to.
+    //  Only takes `&self` so that rows can be claimed from many threads.
+    fn claim(&self, idx: usize) -> bool {
+        if !self.passes_tick_filters(idx) {
+            return false;
         }
+        for &ticks in self.current_write_ticks.as_ref().unwrap() {
+            unsafe { &*ticks }.set_changed(idx, self.this_run);
+        }
+        true
+    }
 
-        if self.storage_idx == self.current_storage_len.unwrap() {
-            self.group_idx += 1;
-            self.current_storage = None;
-            self.current_datas = None;
-            self.current_storage_len = None;
-            return self.next();
+    //  The next claimed row, loading groups as needed.
+    pub(super) fn next_idx(&mut self) -> Option<usize> {
+        loop {
+            self.load_group()?;
+            if self.storage_idx == self.current_storage_len.unwrap() {
+                self.unload_group();
+                continue;
+            }
+            let idx = self.storage_idx;
+            self.storage_idx += 1;
+            if self.claim(idx) {
+                return Some(idx);
+            }
         }
-        let ret = CA::datas(self.current_datas.as_ref().unwrap(), self.storage_idx);
-        self.storage_idx += 1;
-        Some(ret)
+    }
+
+    pub(super) fn get_current_datas(&self) -> &[Option<*const u8>] {
+        self.current_datas.as_ref().unwrap()
+    }
+}
+
+impl<'gal, CA> QueryIter<'gal, CA>
+where
+    CA: ComponentAccessesOptional,
+{
+    //  Row `idx` of the current group, if it passes the tick filters.
+    fn fetch(&self, idx: usize) -> Option<CA> {
+        self.claim(idx)
+            .then(|| CA::datas(self.get_current_datas(), idx))
+    }
+
+    //  Like `fetch`, but for any `'a`, which callers have to bound themselves.
+    fn fetch_item<'a>(&self, idx: usize) -> Option<CA::Item<'a>> {
+        self.claim(idx)
+            .then(|| CA::Item::<'a>::datas(self.get_current_datas(), idx))
+    }
+
+    //  See `QueryInfo::par_for_each`.
+    fn par_for_each<F>(mut self, batch_size: usize, f: F)
+    where
+        CA: Send,
+        F: Fn(CA) + Send + Sync,
+    {
+        //  Rows are disjoint between batches, and ticks are atomic.
+        //  Other threads only read the current group, and the `CA`s they fetch from it are
+        //  `Send`.
+        struct Shared<'a, 'gal, CA>(&'a QueryIter<'gal, CA>);
+        unsafe impl<'a, 'gal, CA: Send> Sync for Shared<'a, 'gal, CA> {}
+
+        let pool = ThreadPool::get_global_pool();
+        let batch_size = batch_size.max(1);
+        //  One group at a time, just like iterating normally.
+        while self.load_group().is_some() {
+            let len = self.current_storage_len.unwrap();
+            let shared = Shared(&self);
+            pool.scope(|s| {
+                for start in (0..len).step_by(batch_size) {
+                    let (shared, f) = (&shared, &f);
+                    s.spawn(move || {
+                        (start..(start + batch_size).min(len))
+                            .filter_map(|idx| shared.0.fetch(idx))
+                            .for_each(f)
+                    });
+                }
+            });
+            self.unload_group();
+        }
+    }
+}
+
+impl<'gal, CA> Iterator for QueryIter<'gal, CA>
+where
+    CA: ComponentAccessesOptional,
+{
+    type Item = CA;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.next_idx()?;
+        Some(CA::datas(self.get_current_datas(), idx))
+    }
+}
+
+/// See [`QueryInfo::iter_combinations`].
+pub struct QueryCombinations<'gal, CA, const K: usize> {
+    groups: Vec<QueryIter<'gal, CA>>,
+    //  Matching `(group, row)`s, indexed by `indices`.
+    rows: Vec<(usize, usize)>,
+    //  Always increasing, so every combination is unordered and distinct.
+    indices: [usize; K],
+    done: bool,
+}
+
+impl<'gal, CA, const K: usize> QueryCombinations<'gal, CA, K>
+where
+    CA: ComponentAccessesOptional,
+{
+    fn fetch_next<T, F>(&mut self, fetch: F) -> Option<[T; K]>
+    where
+        F: Fn(&QueryIter<'gal, CA>, usize) -> Option<T>,
+    {
+        if self.done {
+            None?
+        }
+        let combination = self.indices.map(|i| {
+            let (group_idx, idx) = self.rows[i];
+            //  Tick filters were already checked.
+            fetch(&self.groups[group_idx], idx).unwrap()
+        });
+
+        //  Bump the last index that still has room and reset everything after it.
+        let len = self.rows.len();
+        match (0..K).rev().find(|&i| self.indices[i] < len - K + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..K {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(combination)
+    }
+}
+
+impl<'gal, CA, const K: usize> Iterator for QueryCombinations<'gal, CA, K>
+where
+    CA: ComponentAccessesReadOnly,
+{
+    type Item = [CA; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fetch_next(|group, idx| group.fetch(idx))
+    }
+}
+
+/// A single entity from [`QueryInfo::get`].
+pub struct QueryGet<'gal, CA> {
+    qiter: QueryIter<'gal, CA>,
+    idx: usize,
+}
+
+impl<'gal, CA> QueryGet<'gal, CA>
+where
+    CA: ComponentAccessesOptional,
+{
+    /// Borrows `self` mutably so that `&mut` accesses can't be handed out twice.
+    pub fn get(&mut self) -> CA::Item<'_> {
+        //  Tick filters were already checked.
+        self.qiter.fetch_item(self.idx).unwrap()
     }
 }
 
@@ -210,14 +810,32 @@ where
 }
 
 impl Galaxy {
-    pub fn query<CA: ComponentAccessOptional>(&self) -> QueryInfo<CA> {
+    pub fn query<CA: ComponentAccessesOptional>(&self) -> QueryInfo<CA> {
+        self.query_ticked(self.last_update_tick(), self.next_change_tick())
+    }
+
+    //  Changes are those newer than `last_run`, and `&mut` accesses are stamped with `this_run`.
+    pub(crate) fn query_ticked<CA: ComponentAccessesOptional>(
+        &self,
+        last_run: u64,
+        this_run: u64,
+    ) -> QueryInfo<'_, CA> {
         CA::component_maybe_insert(&self.ctyp);
         QueryInfo {
             incomplete: QueryAccess {
                 accesses: CA::infos(),
-                filters: Vec::new(),
+                filters: CA::any_ofs()
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(idx, ctys)| {
+                        ctys.into_iter()
+                            .map(move |cty| (cty, QueryFilterType::Or(idx)))
+                    })
+                    .collect(),
             },
             galaxy: self,
+            last_run,
+            this_run,
             phantom: PhantomData,
         }
     }
@@ -11,11 +11,14 @@ fn hash_resource_id<RH: Hash + 'static>(rh: RH) -> ResourceId {
     ResourceId::from_hash(hash_type_and_val(rh))
 }
 
-fn hash_resource_type_id<R: 'static>() -> ResourceTypeId {
-    ResourceTypeId::from_hash(hash_type::<R>())
-}
-
 pub trait Resource {
+    fn mewo_resource_type_id() -> ResourceTypeId
+    where
+        Self: 'static + Sized,
+    {
+        ResourceTypeId::from_hash(hash_type::<Self>())
+    }
+
     fn mewo_resource_type_entry() -> TypeEntry
     where
         Self: Sized,
@@ -111,7 +114,7 @@ impl Galaxy {
         r: R,
     ) -> &Self {
         let id = hash_resource_id(rh);
-        let tid = hash_resource_type_id::<R>();
+        let tid = R::mewo_resource_type_id();
         self.resource_maybe_insert::<R>(tid, id);
         let rcp = self.rcp.write();
         {
@@ -134,7 +137,7 @@ impl Galaxy {
         rh: RH,
     ) -> &Self {
         let id = hash_resource_id(rh);
-        let tid = hash_resource_type_id::<R>();
+        let tid = R::mewo_resource_type_id();
         self.resource_maybe_insert::<R>(tid, id);
         let rcp = self.rcp.write();
         {
@@ -150,7 +153,7 @@ impl Galaxy {
         rh: RH,
     ) -> Option<ResourceReadGuard<R>> {
         let id = hash_resource_id(rh);
-        let tid = hash_resource_type_id::<R>();
+        let tid = R::mewo_resource_type_id();
         let rcp = self.rcp.read();
         let rc = rcp.get_read_lock(tid, id).unwrap();
         if rc.is_none() {
@@ -172,7 +175,7 @@ impl Galaxy {
         rh: RH,
     ) -> Option<ResourceWriteGuard<R>> {
         let id = hash_resource_id(rh);
-        let tid = hash_resource_type_id::<R>();
+        let tid = R::mewo_resource_type_id();
         let rcp = self.rcp.read();
         let rc = rcp.get_read_lock(tid, id).unwrap();
         if rc.is_none() {
@@ -189,6 +192,20 @@ impl Galaxy {
         })
     }
 
+    pub fn has_resource<R: Resource + 'static, RH: Hash + 'static>(&self, rh: RH) -> bool {
+        let id = hash_resource_id(rh);
+        let tid = R::mewo_resource_type_id();
+        let rcp = self.rcp.read();
+        //  Unlike `get_resource`, never inserted resources are fine.
+        if let Ok(rc) = rcp.get_read_lock(tid, id) {
+            let exists = rc.is_some();
+            rcp.get_read_unlock(tid, id).unwrap();
+            exists
+        } else {
+            false
+        }
+    }
+
     fn resource_maybe_insert<R: Resource + 'static>(&self, tid: ResourceTypeId, id: ResourceId) {
         let rcp = self.rcp.read();
         if rcp.get_type(tid).is_none() {
@@ -198,4 +215,4 @@ impl Galaxy {
                 .unwrap();
         }
     }
-} 
+}
@@ -81,3 +81,1015 @@ fn test_galaxy_og() {
     assert_eq!(b, &Data(1, 1, 1));
     assert_eq!(c, &Data(1, 0, 0));
 }
+
+#[test]
+fn test_galaxy_query_tuples() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct N<const I: usize>(usize);
+    impl<const I: usize> CheapComponent for N<I> {}
+    impl<const I: usize> GenericComponent for N<I> {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <N<I> as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Skip;
+    impl CheapComponent for Skip {}
+    impl GenericComponent for Skip {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Skip as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let full = galaxy
+        .insert_entity()
+        .insert(N::<0>(0))
+        .insert(N::<1>(1))
+        .insert(N::<2>(2))
+        .insert(N::<3>(3))
+        .insert(N::<4>(4))
+        .insert(N::<5>(5))
+        .insert(N::<6>(6))
+        .insert(N::<7>(7))
+        .insert(N::<8>(8))
+        .insert(N::<9>(9))
+        .insert(N::<10>(10))
+        .insert(N::<11>(11))
+        .get_entity();
+    //  Missing `N<2>`.
+    let partial = galaxy
+        .insert_entity()
+        .insert(N::<0>(0))
+        .insert(N::<1>(1))
+        .insert(N::<3>(3))
+        .insert(N::<4>(4))
+        .insert(N::<5>(5))
+        .insert(N::<6>(6))
+        .get_entity();
+    galaxy
+        .insert_entity()
+        .insert(N::<0>(0))
+        .insert(N::<1>(1))
+        .insert(N::<3>(3))
+        .insert(N::<4>(4))
+        .insert(N::<5>(5))
+        .insert(N::<6>(6))
+        .insert(Skip);
+    galaxy.update();
+
+    let mut seen = Vec::new();
+    for (e, (n0, n1, n2, (n3, (n4, n5)), n6)) in galaxy
+        .query::<(
+            &N<0>,
+            &mut N<1>,
+            Option<&N<2>>,
+            (&N<3>, (&N<4>, &N<5>)),
+            &N<6>,
+        )>()
+        .with::<(N<0>, (N<3>, N<4>), N<5>, N<6>)>()
+        .without::<(Skip,)>()
+        .eiter()
+    {
+        assert_eq!((n0.0, n1.0, n3.0, n4.0, n5.0, n6.0), (0, 1, 3, 4, 5, 6));
+        n1.0 = n0.0 + n3.0 + n4.0 + n5.0 + n6.0 + n2.map(|n2| n2.0).unwrap_or(0);
+        seen.push((e, n2.is_some()));
+    }
+    seen.sort_by_key(|(e, _)| *e == partial);
+    assert_eq!(seen, vec![(full, true), (partial, false)]);
+    galaxy.update();
+
+    let mut count = 0;
+    for (n0, n1, n2, n3, n4, n5, n6, n7, n8, n9, n10, n11) in galaxy
+        .query::<(
+            &N<0>,
+            &N<1>,
+            &N<2>,
+            &N<3>,
+            &N<4>,
+            &N<5>,
+            &N<6>,
+            &N<7>,
+            &N<8>,
+            &N<9>,
+            &N<10>,
+            &N<11>,
+        )>()
+        .iter()
+    {
+        assert_eq!(
+            [n0.0, n1.0, n2.0, n3.0, n4.0, n5.0, n6.0, n7.0, n8.0, n9.0, n10.0, n11.0],
+            [0, 20, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]
+        );
+        count += 1;
+    }
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_galaxy_query_or_any_of() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct N<const I: usize>(usize);
+    impl<const I: usize> CheapComponent for N<I> {}
+    impl<const I: usize> GenericComponent for N<I> {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <N<I> as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let a = galaxy.insert_entity().insert(N::<0>(1)).get_entity();
+    let b = galaxy.insert_entity().insert(N::<1>(2)).get_entity();
+    let ab = galaxy
+        .insert_entity()
+        .insert(N::<0>(3))
+        .insert(N::<1>(4))
+        .get_entity();
+    let c = galaxy.insert_entity().insert(N::<2>(5)).get_entity();
+    galaxy.update();
+
+    let mut any = galaxy
+        .query::<AnyOf<(&N<0>, &N<1>)>>()
+        .eiter()
+        .map(|(e, AnyOf((n0, n1)))| (e.id(), n0.map(|n| n.0), n1.map(|n| n.0)))
+        .collect::<Vec<_>>();
+    any.sort();
+    assert_eq!(
+        any,
+        vec![
+            (a.id(), Some(1), None),
+            (b.id(), None, Some(2)),
+            (ab.id(), Some(3), Some(4)),
+        ]
+    );
+
+    //  Each `AnyOf` and `or` must match separately.
+    let mut both = galaxy
+        .query::<(AnyOf<(&N<0>,)>, AnyOf<(&N<1>, &N<2>)>)>()
+        .eiter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    both.sort();
+    assert_eq!(both, vec![ab.id()]);
+
+    let mut or = galaxy
+        .query::<(Option<&N<0>>, Option<&N<2>>)>()
+        .or::<(N<0>, N<2>)>()
+        .eiter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    or.sort();
+    assert_eq!(or, vec![a.id(), ab.id(), c.id()]);
+
+    let mut or = galaxy
+        .query::<(Option<&N<0>>, Option<&N<2>>)>()
+        .or::<(N<0>, N<2>)>()
+        .or::<(N<1>, N<2>)>()
+        .eiter()
+        .map(|(e, _)| e.id())
+        .collect::<Vec<_>>();
+    or.sort();
+    assert_eq!(or, vec![ab.id(), c.id()]);
+}
+
+#[test]
+fn test_galaxy_query_entity_has() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tag;
+    impl CheapComponent for Tag {}
+    impl GenericComponent for Tag {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tag as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let a = galaxy
+        .insert_entity()
+        .insert(Data(0))
+        .insert(Tag)
+        .get_entity();
+    let b = galaxy.insert_entity().insert(Data(1)).get_entity();
+    let c = galaxy.insert_entity().insert(Tag).get_entity();
+    let d = galaxy.insert_entity().get_entity();
+    galaxy.update();
+
+    let mut found = galaxy
+        .query::<(Entity, &Data, Has<Tag>)>()
+        .iter()
+        .map(|(e, data, tag)| (e.id(), data.0, *tag))
+        .collect::<Vec<_>>();
+    found.sort();
+    assert_eq!(found, vec![(a.id(), 0, true), (b.id(), 1, false)]);
+
+    let mut all = galaxy
+        .query::<Entity>()
+        .iter()
+        .map(|e| e.id())
+        .collect::<Vec<_>>();
+    all.sort();
+    assert_eq!(all, vec![a.id(), b.id(), c.id(), d.id()]);
+
+    //  `Has` doesn't lock, so this can't deadlock.
+    for _ in galaxy.query::<&mut Data>().iter() {
+        let has = galaxy
+            .query::<(Entity, Has<Data>)>()
+            .with::<Tag>()
+            .iter()
+            .map(|(e, has)| (e.id(), *has))
+            .collect::<Vec<_>>();
+        assert_eq!(has.len(), 2);
+        assert!(has.contains(&(a.id(), true)));
+        assert!(has.contains(&(c.id(), false)));
+    }
+
+    let (e, tag) = galaxy
+        .get_entity(b)
+        .unwrap()
+        .get::<(Entity, Has<Tag>)>()
+        .unwrap()
+        .get();
+    assert_eq!((e, *tag), (b, false));
+}
+
+//  Moving an entity to another storage must leave the rest of the old storage intact.
+#[test]
+fn test_galaxy_move_entity() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tag;
+    impl CheapComponent for Tag {}
+    impl GenericComponent for Tag {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tag as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let entities = (0..4)
+        .map(|i| galaxy.insert_entity().insert(Data(i)).get_entity())
+        .collect::<Vec<_>>();
+    galaxy.update();
+    galaxy.get_entity(entities[1]).unwrap().insert(Tag);
+    galaxy.update();
+
+    let mut found = galaxy
+        .query::<(Entity, &Data, Has<Tag>)>()
+        .iter()
+        .map(|(e, data, tag)| (e, data.0, *tag))
+        .collect::<Vec<_>>();
+    found.sort_by_key(|(_, data, _)| *data);
+    assert_eq!(
+        found,
+        vec![
+            (entities[0], 0, false),
+            (entities[1], 1, true),
+            (entities[2], 2, false),
+            (entities[3], 3, false),
+        ]
+    );
+}
+
+#[test]
+fn test_galaxy_query_par_for_each() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tag;
+    impl CheapComponent for Tag {}
+    impl GenericComponent for Tag {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tag as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    for i in 0..1000 {
+        let mut entity = galaxy.insert_entity();
+        entity.insert(Data(i));
+        if i % 3 == 0 {
+            entity.insert(Tag);
+        }
+    }
+    galaxy.update();
+
+    let count = AtomicUsize::new(0);
+    galaxy
+        .query::<(Entity, &mut Data)>()
+        .par_for_each(64, |(_, data)| {
+            data.0 *= 2;
+            count.fetch_add(1, Ordering::SeqCst);
+        });
+    assert_eq!(count.load(Ordering::SeqCst), 1000);
+    galaxy.update();
+
+    let sum = AtomicUsize::new(0);
+    galaxy
+        .query::<&Data>()
+        .with::<Tag>()
+        .par_for_each(7, |data| {
+            sum.fetch_add(data.0, Ordering::SeqCst);
+        });
+    assert_eq!(
+        sum.load(Ordering::SeqCst),
+        (0..1000)
+            .filter(|i| i % 3 == 0)
+            .map(|i| i * 2)
+            .sum::<usize>()
+    );
+}
+
+#[test]
+fn test_galaxy_query_get() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tag;
+    impl CheapComponent for Tag {}
+    impl GenericComponent for Tag {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tag as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let a = galaxy
+        .insert_entity()
+        .insert(Data(0))
+        .insert(Tag)
+        .get_entity();
+    let b = galaxy.insert_entity().insert(Data(1)).get_entity();
+    let c = galaxy.insert_entity().insert(Tag).get_entity();
+    galaxy.update();
+
+    //  Not updated yet.
+    let d = galaxy.insert_entity().insert(Data(3)).get_entity();
+    assert!(galaxy.query::<&Data>().get(d).is_none());
+    galaxy.update();
+
+    assert_eq!(galaxy.query::<&Data>().get(b).unwrap().get(), &Data(1));
+    assert!(galaxy.query::<&Data>().get(c).is_none());
+    assert!(galaxy.query::<&Data>().with::<Tag>().get(b).is_none());
+    assert_eq!(
+        galaxy
+            .query::<(Entity, &Data)>()
+            .with::<Tag>()
+            .get(a)
+            .unwrap()
+            .get(),
+        (a, &Data(0))
+    );
+
+    galaxy.query::<&mut Data>().get(b).unwrap().get().0 = 10;
+    galaxy.update();
+    assert_eq!(galaxy.query::<&Data>().get(b).unwrap().get(), &Data(10));
+
+    //  Only `b` changed, and getting doesn't queue a modify.
+    let changed = galaxy.query::<&Data>().changed::<Data>();
+    assert!(changed.get(a).is_none());
+    let changed = galaxy.query::<&Data>().changed::<Data>();
+    assert!(changed.get(b).is_some());
+    galaxy.update();
+    assert!(galaxy.query::<&Data>().changed::<Data>().get(b).is_none());
+}
+
+#[test]
+fn test_galaxy_query_single_count() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Player;
+    impl CheapComponent for Player {}
+    impl GenericComponent for Player {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Player as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    assert!(galaxy.query::<&Data>().is_empty());
+    assert!(matches!(
+        galaxy.query::<&Data>().get_single().err().unwrap().error,
+        ErrorType::QuerySingle { count: 0 }
+    ));
+
+    for i in 0..10 {
+        galaxy.insert_entity().insert(Data(i));
+    }
+    let player = galaxy
+        .insert_entity()
+        .insert(Data(10))
+        .insert(Player)
+        .get_entity();
+    galaxy.update();
+
+    assert_eq!(galaxy.query::<&Data>().count(), 11);
+    assert!(!galaxy.query::<&Data>().is_empty());
+    assert_eq!(galaxy.query::<&Data>().with::<Player>().count(), 1);
+    assert!(matches!(
+        galaxy.query::<&Data>().get_single().err().unwrap().error,
+        ErrorType::QuerySingle { count: 11 }
+    ));
+
+    let mut single = galaxy
+        .query::<(Entity, &mut Data)>()
+        .with::<Player>()
+        .single();
+    let (e, data) = single.get();
+    assert_eq!(e, player);
+    data.0 = 20;
+    drop(single);
+    galaxy.update();
+
+    assert_eq!(galaxy.query::<&Data>().changed::<Data>().count(), 1);
+    assert_eq!(
+        galaxy.query::<&Data>().changed::<Data>().single().get(),
+        &Data(20)
+    );
+    galaxy.update();
+    assert!(galaxy.query::<&Data>().changed::<Data>().is_empty());
+}
+
+#[test]
+fn test_galaxy_query_combinations() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tag;
+    impl CheapComponent for Tag {}
+    impl GenericComponent for Tag {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tag as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    //  Split across two groups.
+    for i in 0..5 {
+        let mut entity = galaxy.insert_entity();
+        entity.insert(Data(i));
+        if i % 2 == 0 {
+            entity.insert(Tag);
+        }
+    }
+    galaxy.update();
+
+    let mut pairs = galaxy
+        .query::<&Data>()
+        .iter_combinations::<2>()
+        .map(|[a, b]| (a.0.min(b.0), a.0.max(b.0)))
+        .collect::<Vec<_>>();
+    pairs.sort();
+    let mut expected = vec![];
+    for a in 0..5 {
+        for b in a + 1..5 {
+            expected.push((a, b));
+        }
+    }
+    assert_eq!(pairs, expected);
+
+    assert_eq!(galaxy.query::<&Data>().iter_combinations::<3>().count(), 10);
+    assert_eq!(
+        galaxy
+            .query::<&Data>()
+            .with::<Tag>()
+            .iter_combinations::<4>()
+            .count(),
+        0
+    );
+
+    //  Everyone adds their value onto everyone else.
+    galaxy
+        .query::<&mut Data>()
+        .for_each_combination(|[a, b]: [&mut Data; 2]| {
+            let (a_value, b_value) = (a.0 % 100, b.0 % 100);
+            a.0 += b_value * 100;
+            b.0 += a_value * 100;
+        });
+    galaxy.update();
+
+    let mut values = galaxy
+        .query::<&Data>()
+        .iter()
+        .map(|data| data.0)
+        .collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(
+        values,
+        (0..5).map(|i| i + (10 - i) * 100).rev().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_galaxy_dynamic_query() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Other(u8);
+    impl CheapComponent for Other {}
+    impl GenericComponent for Other {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Other as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let a = galaxy
+        .insert_entity()
+        .insert(Data(0))
+        .insert(Other(7))
+        .get_entity();
+    let b = galaxy.insert_entity().insert(Data(1)).get_entity();
+    galaxy.update();
+
+    let data = galaxy
+        .get_component_type_id(std::any::type_name::<Data>())
+        .unwrap();
+    let other = Other::mewo_component_id();
+    assert_eq!(galaxy.get_component_type_id("nothing"), None);
+    assert_eq!(
+        galaxy.get_component_type(data).unwrap().size,
+        std::mem::size_of::<Data>()
+    );
+
+    let iter = galaxy
+        .dynamic_query()
+        .write(data)
+        .option_read(other)
+        .iter()
+        .unwrap();
+    assert_eq!(iter.get_types()[1].size, 1);
+    let mut found = iter
+        .map(|row| {
+            let data = unsafe { &mut *(row.datas[0].unwrap() as *mut Data) };
+            data.0 += 10;
+            let other = row.datas[1].map(|other| unsafe { *(other as *const Other) });
+            (row.entity.id(), data.0, other)
+        })
+        .collect::<Vec<_>>();
+    found.sort_by_key(|&(id, _, _)| id);
+    let mut expected = vec![(a.id(), 10, Some(Other(7))), (b.id(), 11, None)];
+    expected.sort_by_key(|&(id, _, _)| id);
+    assert_eq!(found, expected);
+    galaxy.update();
+
+    //  Same as a typed query.
+    let mut values = galaxy
+        .query::<&Data>()
+        .without::<Other>()
+        .iter()
+        .map(|data| data.0)
+        .collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, vec![11]);
+    assert_eq!(
+        galaxy
+            .dynamic_query()
+            .read(data)
+            .changed(data)
+            .iter()
+            .unwrap()
+            .count(),
+        2
+    );
+
+    //  Not known yet.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Unknown;
+    impl CheapComponent for Unknown {}
+    impl GenericComponent for Unknown {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Unknown as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+    assert!(matches!(
+        galaxy
+            .dynamic_query()
+            .read(Unknown::mewo_component_id())
+            .iter()
+            .err()
+            .unwrap()
+            .error,
+        ErrorType::ComponentTypePlanetGetType { .. }
+    ));
+}
+
+#[test]
+fn test_galaxy_query_try_iter() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health(usize);
+    impl UniqueComponent for Health {}
+    impl GenericComponent for Health {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Health as UniqueComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let a = galaxy
+        .insert_entity()
+        .insert(Health(10))
+        .insert(Data(0))
+        .get_entity();
+    galaxy.update();
+
+    for _ in galaxy.query::<&mut Health>().iter() {
+        let err = galaxy.query::<(&Health, &Data)>().try_iter().err().unwrap();
+        assert!(matches!(
+            err.error,
+            ErrorType::QueryLockConflict { components }
+                if components == vec![std::any::type_name::<Health>().to_string()]
+        ));
+        assert!(galaxy.query::<&Health>().try_get(a).is_err());
+        //  Nothing was left locked by the failed attempt.
+        assert_eq!(galaxy.query::<&Data>().try_iter().unwrap().count(), 1);
+    }
+
+    //  Reads don't conflict with each other.
+    for _ in galaxy.query::<&Health>().iter() {
+        assert_eq!(
+            galaxy.query::<&Health>().try_get(a).unwrap().unwrap().get(),
+            &Health(10)
+        );
+        assert!(galaxy.query::<&mut Health>().try_iter().is_err());
+    }
+    assert_eq!(galaxy.query::<&mut Health>().try_iter().unwrap().count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "already locked by this thread")]
+fn test_galaxy_debug_locks() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health(usize);
+    impl UniqueComponent for Health {}
+    impl GenericComponent for Health {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Health as UniqueComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    galaxy.set_debug_locks(true);
+    galaxy.insert_entity().insert(Health(10));
+    galaxy.update();
+
+    for _ in galaxy.query::<&Health>().iter() {
+        for _ in galaxy.query::<&mut Health>().iter() {}
+    }
+}
+
+#[test]
+fn test_galaxy_query_prev_live() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health(usize);
+    impl UniqueComponent for Health {}
+    impl GenericComponent for Health {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Health as UniqueComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    galaxy.insert_entity().insert(Data(1)).insert(Health(1));
+    galaxy.update();
+    galaxy.update();
+
+    for (data, health) in galaxy.query::<(&mut Data, &mut Health)>().iter() {
+        data.0 = 2;
+        health.0 = 2;
+    }
+
+    for (prev, live, data) in galaxy.query::<(Prev<&Data>, Live<&Data>, &Data)>().iter() {
+        assert_eq!(*prev, Data(1));
+        assert_eq!(*live, Data(2));
+        assert_eq!(*data, Data(1));
+    }
+    //  Only copycat components have a previous frame.
+    for (prev, live) in galaxy.query::<(Prev<&Health>, Live<&Health>)>().iter() {
+        assert_eq!(*prev, Health(2));
+        assert_eq!(*live, Health(2));
+    }
+    galaxy.update();
+
+    for (prev, live) in galaxy.query::<(Prev<&Data>, Live<&Data>)>().iter() {
+        assert_eq!(*prev, Data(2));
+        assert_eq!(*live, Data(2));
+    }
+    galaxy.update();
+
+    //  `Live` doesn't count as a change.
+    assert!(galaxy.query::<&Data>().changed::<Data>().is_empty());
+}
+
+#[test]
+fn test_galaxy_component_storage() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tile(u8);
+    impl CheapComponent for Tile {}
+    impl GenericComponent for Tile {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tile as CheapComponent>::mewo_component_duplicate()
+        }
+        fn mewo_component_storage() -> ComponentStorage {
+            ComponentStorage::Direct
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    galaxy.insert_entity().insert(Tile(0)).insert(Data(0));
+    galaxy.update();
+
+    for (tile, data) in galaxy.query::<(&mut Tile, &mut Data)>().iter() {
+        tile.0 = 1;
+        data.0 = 1;
+    }
+    //  Direct writes are seen right away.
+    for (tile, data) in galaxy.query::<(&Tile, &Data)>().iter() {
+        assert_eq!(*tile, Tile(1));
+        assert_eq!(*data, Data(0));
+    }
+    galaxy.update();
+    for (tile, data) in galaxy.query::<(&Tile, &Data)>().iter() {
+        assert_eq!(*tile, Tile(1));
+        assert_eq!(*data, Data(1));
+    }
+}
+
+#[test]
+fn test_galaxy_copycat_dirty_rows() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Marker;
+    impl CheapComponent for Marker {}
+    impl GenericComponent for Marker {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Marker as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let entities = (0..4)
+        .map(|i| galaxy.insert_entity().insert(Data(i)).get_entity())
+        .collect::<Vec<_>>();
+    galaxy.update();
+
+    let get = |galaxy: &Galaxy, e| *galaxy.get_entity(e).unwrap().get::<&Data>().unwrap().get();
+
+    //  Single row writes.
+    galaxy
+        .get_entity(entities[1])
+        .unwrap()
+        .get::<&mut Data>()
+        .unwrap()
+        .get()
+        .0 = 10;
+    assert_eq!(get(&galaxy, entities[1]), Data(1));
+    galaxy.update();
+    assert_eq!(get(&galaxy, entities[1]), Data(10));
+
+    //  Nothing written, nothing lost.
+    for _ in galaxy.query::<Live<&Data>>().iter() {}
+    galaxy.update();
+    let datas = entities
+        .iter()
+        .map(|&e| get(&galaxy, e))
+        .collect::<Vec<_>>();
+    assert_eq!(datas, [Data(0), Data(10), Data(2), Data(3)]);
+
+    //  Removing an entity moves the last row into its place.
+    galaxy.remove_entity(entities[0]);
+    galaxy.update();
+    let datas = entities[1..]
+        .iter()
+        .map(|&e| get(&galaxy, e))
+        .collect::<Vec<_>>();
+    assert_eq!(datas, [Data(10), Data(2), Data(3)]);
+
+    //  Moving storages.
+    galaxy.get_entity(entities[2]).unwrap().insert(Marker);
+    galaxy.update();
+    for data in galaxy.query::<&mut Data>().with::<Marker>().iter() {
+        data.0 += 1;
+    }
+    galaxy.update();
+    let datas = entities[1..]
+        .iter()
+        .map(|&e| get(&galaxy, e))
+        .collect::<Vec<_>>();
+    assert_eq!(datas, [Data(10), Data(3), Data(3)]);
+}
+
+#[test]
+fn test_galaxy_insert_existing_component() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Data(usize);
+    impl CheapComponent for Data {}
+    impl GenericComponent for Data {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Data as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tag;
+    impl CheapComponent for Tag {}
+    impl GenericComponent for Tag {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tag as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    let a = galaxy.insert_entity().insert(Data(1)).get_entity();
+    let b = galaxy.insert_entity().insert(Data(2)).get_entity();
+    galaxy.update();
+    galaxy.update();
+    assert_eq!(galaxy.query::<&Data>().changed::<Data>().count(), 0);
+
+    //  Same group, so the value is replaced in place.
+    galaxy.get_entity(a).unwrap().insert(Data(7));
+    galaxy.update();
+    assert_eq!(galaxy.query::<&Data>().get(a).unwrap().get(), &Data(7));
+    assert_eq!(galaxy.query::<&Data>().get(b).unwrap().get(), &Data(2));
+    let changed = galaxy.query::<Entity>().changed::<Data>();
+    assert_eq!(changed.iter().collect::<Vec<_>>(), vec![a]);
+
+    //  Moving to another group keeps the new value too.
+    galaxy.get_entity(b).unwrap().insert(Data(9)).insert(Tag);
+    galaxy.update();
+    assert_eq!(galaxy.query::<&Data>().get(b).unwrap().get(), &Data(9));
+    assert_eq!(galaxy.query::<&Data>().get(a).unwrap().get(), &Data(7));
+}
+
+#[test]
+fn test_galaxy_entity_get_prev_live() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Health(usize);
+    impl UniqueComponent for Health {}
+    impl GenericComponent for Health {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Health as UniqueComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tile(u8);
+    impl CheapComponent for Tile {}
+    impl GenericComponent for Tile {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Tile as CheapComponent>::mewo_component_duplicate()
+        }
+        fn mewo_component_storage() -> ComponentStorage {
+            ComponentStorage::Direct
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    galaxy.set_debug_locks(true);
+    let e = galaxy
+        .insert_entity()
+        .insert(Health(3))
+        .insert(Tile(1))
+        .get_entity();
+    galaxy.update();
+
+    //  Both read the same column, which is only locked once.
+    {
+        let mut entity = galaxy.get_entity(e).unwrap();
+        let getter = entity.get::<(Prev<&Health>, Live<&Health>, Prev<&Tile>, Live<&Tile>)>();
+        let (prev_health, live_health, prev_tile, live_tile) = getter.unwrap().get();
+        assert_eq!((prev_health.0, live_health.0), (&Health(3), &Health(3)));
+        assert_eq!((prev_tile.0, live_tile.0), (&Tile(1), &Tile(1)));
+    }
+
+    //  Everything was unlocked.
+    galaxy
+        .get_entity(e)
+        .unwrap()
+        .get::<&mut Health>()
+        .unwrap()
+        .get()
+        .0 = 4;
+    assert_eq!(galaxy.query::<&Health>().iter().next(), Some(&Health(4)));
+}
+
+#[test]
+fn test_galaxy_component_storage_derive() {
+    use mewo_ecs_derive::CheapComponent;
+
+    #[derive(Debug, Clone, Copy, PartialEq, CheapComponent)]
+    #[mewo(storage = "direct")]
+    struct Tile(u8);
+
+    #[derive(Debug, Clone, Copy, PartialEq, CheapComponent)]
+    struct Data(u8);
+
+    assert_eq!(Tile::mewo_component_storage(), ComponentStorage::Direct);
+    assert_eq!(
+        Data::mewo_component_storage(),
+        ComponentStorage::DoubleBuffered
+    );
+
+    let mut galaxy = Galaxy::new();
+    galaxy.insert_entity().insert(Tile(0)).insert(Data(0));
+    galaxy.update();
+
+    for (tile, data) in galaxy.query::<(&mut Tile, &mut Data)>().iter() {
+        tile.0 = 1;
+        data.0 = 1;
+    }
+    for (tile, data) in galaxy.query::<(&Tile, &Data)>().iter() {
+        assert_eq!(*tile, Tile(1));
+        assert_eq!(*data, Data(0));
+    }
+}
@@ -4,14 +4,22 @@ mod galaxy;
 mod log;
 pub mod run;
 
-pub use data::{Preserve, PreserveInstance, ValueDuplicate};
-pub use ecs::Entity;
+pub use data::{Preserve, PreserveInstance, TypeEntry, ValueDuplicate};
+pub use ecs::{ComponentStorage, ComponentTypeId, ECSError, Entity, ErrorType};
 pub use galaxy::{
-    CheapComponent, Component, EntityGetter, Event, Galaxy, GenericComponent, Resource,
-    ResourceReadGuard, ResourceWriteGuard, UniqueComponent,
+    AnyOf, CheapComponent, Component, ComponentAccessAnyOf, ComponentAccessesAnyOf,
+    ComponentAccessesReadOnly, DynamicQueryInfo, DynamicQueryIter, DynamicRow, EntityGetter, Event,
+    Exit, Galaxy, GenericComponent, Has, Live, Prev, Resource, ResourceReadGuard,
+    ResourceWriteGuard, UniqueComponent,
 };
 pub use log::{LogEvent, LogFold, LogRecord, LogSubscription, LogTarget, Logger};
-pub use run::{run_single, run_spawn, run_spawn_locked};
+pub use run::{
+    run_fixed, run_parallel, run_single, run_spawn, run_spawn_fixed, run_spawn_locked,
+    run_spawn_parallel, run_spawn_stages, run_stages, with_local, BoxedExclusiveSystem,
+    BoxedSystem, Clock, Condition, Events, FixedTime, FixedTimestep, IntoSystem, Local,
+    ManualClock, Plugin, Query, Res, ResMut, RunControl, Schedule, ScheduleBuilder, Stage, Stages,
+    StagesBuilder, System, SystemClock, SystemParam, Time,
+};
 
 pub use parking_lot::RwLock;
 pub use std::sync::Arc;
@@ -0,0 +1,62 @@
+use super::*;
+use std::{hash::Hash, ops::Not};
+
+/// A predicate that decides whether a system or a labeled set of systems runs this frame.
+/// Conditions are evaluated once per frame before any system in the schedule starts.
+pub struct Condition(Box<dyn FnMut(&Galaxy) -> bool + Send>);
+
+impl Condition {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut(&Galaxy) -> bool + Send + 'static,
+    {
+        Condition(Box::new(f))
+    }
+
+    /// Both conditions are always evaluated so that stateful conditions like
+    /// [`Condition::every_n_frames`] keep counting.
+    pub fn and(mut self, mut other: Condition) -> Self {
+        Condition::new(move |g| self.check(g) & other.check(g))
+    }
+
+    /// Both conditions are always evaluated, see [`Condition::and`].
+    pub fn or(mut self, mut other: Condition) -> Self {
+        Condition::new(move |g| self.check(g) | other.check(g))
+    }
+
+    /// True while the resource `R` associated with `rh` exists.
+    pub fn resource_exists<R, RH>(rh: RH) -> Self
+    where
+        R: Resource + 'static,
+        RH: Clone + Hash + Send + 'static,
+    {
+        Condition::new(move |g| g.has_resource::<R, _>(rh.clone()))
+    }
+
+    /// True when at least one `E` was inserted last frame.
+    pub fn on_event<E: Event + 'static>() -> Self {
+        Condition::new(|g| !g.get_events::<E>().is_empty())
+    }
+
+    /// True on the first frame and every `n` frames after that.
+    pub fn every_n_frames(n: usize) -> Self {
+        let mut frame = 0;
+        Condition::new(move |_| {
+            let ret = frame % n.max(1) == 0;
+            frame += 1;
+            ret
+        })
+    }
+
+    pub(super) fn check(&mut self, galaxy: &Galaxy) -> bool {
+        (self.0)(galaxy)
+    }
+}
+
+impl Not for Condition {
+    type Output = Condition;
+
+    fn not(mut self) -> Self::Output {
+        Condition::new(move |g| !self.check(g))
+    }
+}
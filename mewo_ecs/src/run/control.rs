@@ -0,0 +1,142 @@
+use super::*;
+use parking_lot::{Condvar, Mutex};
+use std::{any::TypeId, time::Duration};
+
+struct ControlState {
+    paused: bool,
+    steps: usize,
+    until: Option<Condition>,
+    //  Bumped by every command so that `advance` can tell if it raced with one.
+    generation: u64,
+}
+
+/// Pauses, resumes and steps a runner, see [`StagesBuilder::control`].
+/// Clones control the same runner, so one can be kept on another thread.
+/// It is also inserted as a single resource so systems can drive it, for example, from a key
+/// event.
+///
+/// While paused, the runner waits for the control to change, but wakes up every
+/// [`RunControl::PAUSED_FRAME`] so that systems marked with [`System::run_while_paused`] can run.
+/// The galaxy is only updated if one of them ran, and [`Time`] doesn't advance.
+#[derive(Clone)]
+pub struct RunControl {
+    state: Arc<(Mutex<ControlState>, Condvar)>,
+}
+
+impl Resource for RunControl {}
+
+impl RunControl {
+    /// How long a paused runner waits between paused frames.
+    pub const PAUSED_FRAME: Duration = Duration::from_millis(16);
+
+    pub fn new() -> Self {
+        RunControl {
+            state: Arc::new((
+                Mutex::new(ControlState {
+                    paused: false,
+                    steps: 0,
+                    until: None,
+                    generation: 0,
+                }),
+                Condvar::new(),
+            )),
+        }
+    }
+
+    pub fn single_resource() -> TypeId {
+        TypeId::of::<RunControl>()
+    }
+
+    pub fn pause(&self) {
+        self.command(|state| {
+            state.paused = true;
+            state.steps = 0;
+            state.until = None;
+        })
+    }
+
+    pub fn resume(&self) {
+        self.command(|state| {
+            state.paused = false;
+            state.steps = 0;
+            state.until = None;
+        })
+    }
+
+    /// Pause after running `frames` more frames.
+    pub fn step(&self, frames: usize) {
+        self.command(|state| {
+            state.paused = true;
+            state.steps += frames;
+            state.until = None;
+        })
+    }
+
+    /// Resume until `condition` is true at the start of a frame, then pause before that frame.
+    pub fn run_until(&self, condition: Condition) {
+        self.command(|state| {
+            state.paused = false;
+            state.steps = 0;
+            state.until = Some(condition);
+        })
+    }
+
+    /// Whether the next frame won't run normally.
+    pub fn is_paused(&self) -> bool {
+        let state = self.state.0.lock();
+        state.paused && state.steps == 0
+    }
+
+    fn command<F: FnOnce(&mut ControlState)>(&self, f: F) {
+        let mut state = self.state.0.lock();
+        f(&mut state);
+        state.generation += 1;
+        self.state.1.notify_all();
+    }
+
+    //  Called at the start of each frame, returns whether the frame runs normally.
+    pub(super) fn advance(&self, galaxy: &Galaxy) -> bool {
+        //  The condition can read resources that systems hold along with this control, so it's
+        //  checked without holding the lock.
+        let (mut until, generation) = {
+            let mut state = self.state.0.lock();
+            (state.until.take(), state.generation)
+        };
+        let reached = until.as_mut().map(|until| until.check(galaxy));
+
+        let mut state = self.state.0.lock();
+        //  Newer commands win over the condition.
+        if state.generation == generation {
+            match reached {
+                Some(true) => state.paused = true,
+                Some(false) => state.until = until,
+                None => {}
+            }
+        }
+        if !state.paused {
+            true
+        } else if state.steps != 0 {
+            state.steps -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    //  Blocks for up to `timeout` while paused, returns whether a command came in.
+    pub(super) fn wait_paused(&self, timeout: Duration) -> bool {
+        let mut state = self.state.0.lock();
+        let generation = state.generation;
+        if !state.paused || state.steps != 0 {
+            return true;
+        }
+        self.state.1.wait_for(&mut state, timeout);
+        state.generation != generation
+    }
+}
+
+impl Default for RunControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
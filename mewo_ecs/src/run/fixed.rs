@@ -0,0 +1,119 @@
+use super::*;
+use std::{any::TypeId, time::Duration};
+
+/// How often [`run_fixed`](super::run_fixed) steps its fixed schedule.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTimestep {
+    step: Duration,
+    max_steps: usize,
+}
+
+impl FixedTimestep {
+    pub fn new(step: Duration) -> Self {
+        FixedTimestep { step, max_steps: 8 }
+    }
+
+    /// Step `hz` times per second.
+    ///
+    /// # Panics
+    ///
+    /// If `hz` is zero, negative, NaN, or so small that the step overflows a [`Duration`].
+    pub fn from_hz(hz: f64) -> Self {
+        match Duration::try_from_secs_f64(1.0 / hz) {
+            Ok(step) => Self::new(step),
+            Err(_) => panic!("FixedTimestep::from_hz needs a positive rate, got {}", hz),
+        }
+    }
+
+    /// The most steps that can be run in one frame when catching up after a hitch.
+    /// Any time beyond that is dropped.
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    pub fn get_step(&self) -> Duration {
+        self.step
+    }
+}
+
+/// Inserted by [`run_fixed`](super::run_fixed) before each frame.
+/// Use `alpha` to blend between the previous and current fixed step when rendering.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTime {
+    step: Duration,
+    alpha: f32,
+}
+
+impl Resource for FixedTime {}
+
+impl FixedTime {
+    pub fn single_resource() -> TypeId {
+        TypeId::of::<FixedTime>()
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// How far between the last fixed step and the next one we are, from 0 to 1.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+}
+
+pub(super) struct FixedAccumulator {
+    timestep: FixedTimestep,
+    accumulated: Duration,
+}
+
+impl FixedAccumulator {
+    pub fn new(timestep: FixedTimestep) -> Self {
+        FixedAccumulator {
+            timestep,
+            accumulated: Duration::ZERO,
+        }
+    }
+
+    //  Returns the number of fixed steps to run this frame.
+    pub fn advance(&mut self, elapsed: Duration) -> usize {
+        let step = self.timestep.step;
+        if step.is_zero() {
+            return 1;
+        }
+        self.accumulated += elapsed;
+        let mut steps = 0;
+        while self.accumulated >= step && steps < self.timestep.max_steps {
+            self.accumulated -= step;
+            steps += 1;
+        }
+        //  Drop whole steps that we couldn't catch up on, otherwise we never recover.
+        if self.accumulated >= step {
+            self.accumulated =
+                Duration::from_nanos((self.accumulated.as_nanos() % step.as_nanos()) as u64);
+        }
+        steps
+    }
+
+    pub fn get_fixed_time(&self) -> FixedTime {
+        let step = self.timestep.step;
+        FixedTime {
+            step,
+            alpha: if step.is_zero() {
+                1.0
+            } else {
+                (self.accumulated.as_secs_f64() / step.as_secs_f64()) as f32
+            },
+        }
+    }
+}
+
+pub(super) fn insert_fixed_time(galaxy: &Galaxy, fixed_time: FixedTime) {
+    if galaxy.has_resource::<FixedTime, _>(FixedTime::single_resource()) {
+        **galaxy
+            .get_mut_resource::<FixedTime, _>(FixedTime::single_resource())
+            .unwrap() = fixed_time;
+    } else {
+        galaxy.insert_resource(FixedTime::single_resource(), fixed_time);
+    }
+}
@@ -0,0 +1,362 @@
+use super::*;
+use std::thread;
+
+mod condition;
+mod control;
+mod fixed;
+mod param;
+mod plugin;
+mod schedule;
+mod stage;
+mod system;
+mod time;
+
+#[cfg(test)]
+mod test;
+
+pub use condition::Condition;
+pub use control::RunControl;
+pub use fixed::{FixedTime, FixedTimestep};
+pub use param::{with_local, Events, IntoSystem, Local, Query, Res, ResMut, SystemParam};
+pub use plugin::Plugin;
+pub use schedule::{Schedule, ScheduleBuilder};
+pub use stage::{BoxedExclusiveSystem, Stage, Stages, StagesBuilder};
+pub use system::{BoxedSystem, System, SystemAccess};
+pub use time::{Clock, ManualClock, SystemClock, Time};
+
+/// Block the current thread, driving the `galaxy` with `systems`.
+///
+/// All runners insert a [`Time`] if there isn't one already and tick it after each update.
+pub fn run_single(mut galaxy: Galaxy, mut systems: Vec<BoxedSystem>) -> Exit {
+    time::time_maybe_insert(&galaxy);
+    loop {
+        systems.iter_mut().for_each(|sys| sys(&galaxy));
+        if galaxy.update().is_none() {
+            return galaxy.get_exit().unwrap_or_default();
+        }
+        time::time_tick(&galaxy);
+    }
+}
+
+/// Spawn a new thread and drive `galaxy` with `systems`.
+/// `pre_update` and `post_update` are called before updating and after updating respectively.
+/// `pre_update` and `post_update` don't lock `galaxy`, so they don't impose side effects on other
+/// threads.
+/// In other words, you can safely block or wait in these functions.
+///
+/// Compared to [`self::run_spawn`], this function read locks `galaxy` once meaning that other
+/// threads cannot update until all systems have completed.
+pub fn run_spawn_locked(
+    galaxy: Arc<RwLock<Galaxy>>,
+    mut systems: Vec<BoxedSystem>,
+    pre_update: fn(&Arc<RwLock<Galaxy>>),
+    post_update: fn(&Arc<RwLock<Galaxy>>),
+) -> thread::JoinHandle<Exit> {
+    time::time_maybe_insert(&galaxy.read());
+    thread::spawn(move || loop {
+        pre_update(&galaxy);
+
+        {
+            let galaxy = galaxy.read();
+            systems.iter_mut().for_each(|sys| sys(&galaxy));
+        }
+        {
+            let mut galaxy = galaxy.write();
+            if galaxy.update().is_none() {
+                return galaxy.get_exit().unwrap_or_default();
+            }
+            time::time_tick(&galaxy);
+        }
+
+        post_update(&galaxy);
+    })
+}
+
+/// Spawn a new thread and drive `galaxy` with `systems`.
+/// `pre_update` and `post_update` are called before updating and after updating respectively.
+/// `pre_update` and `post_update` don't lock `galaxy`, so they don't impose side effects on other
+/// threads.
+/// In other words, you can safely block or wait in these functions.
+///
+/// Compared to [`self::run_spawn_locked`], this function read locks `galaxy` on each system call.
+/// This means that other threads can update between system calls.
+pub fn run_spawn(
+    galaxy: Arc<RwLock<Galaxy>>,
+    mut systems: Vec<BoxedSystem>,
+    pre_update: fn(&Arc<RwLock<Galaxy>>),
+    post_update: fn(&Arc<RwLock<Galaxy>>),
+) -> thread::JoinHandle<Exit> {
+    time::time_maybe_insert(&galaxy.read());
+    thread::spawn(move || loop {
+        pre_update(&galaxy);
+
+        {
+            systems.iter_mut().for_each(|sys| {
+                let galaxy = galaxy.read();
+                sys(&galaxy)
+            });
+        }
+        {
+            let mut galaxy = galaxy.write();
+            if galaxy.update().is_none() {
+                return galaxy.get_exit().unwrap_or_default();
+            }
+            time::time_tick(&galaxy);
+        }
+
+        post_update(&galaxy);
+    })
+}
+
+/// Block the current thread, driving the `galaxy` with `schedule`.
+/// Unlike [`self::run_single`], systems that don't conflict run in parallel.
+pub fn run_parallel(mut galaxy: Galaxy, mut schedule: Schedule) -> Exit {
+    time::time_maybe_insert(&galaxy);
+    loop {
+        schedule.run(&galaxy);
+        if galaxy.update().is_none() {
+            return galaxy.get_exit().unwrap_or_default();
+        }
+        time::time_tick(&galaxy);
+    }
+}
+
+/// Spawn a new thread and drive `galaxy` with `schedule`.
+/// `pre_update` and `post_update` behave the same as in [`self::run_spawn_locked`].
+///
+/// Like [`self::run_spawn_locked`], `galaxy` is read locked until all systems have completed.
+/// Unlike [`self::run_spawn_locked`], systems that don't conflict run in parallel.
+pub fn run_spawn_parallel(
+    galaxy: Arc<RwLock<Galaxy>>,
+    mut schedule: Schedule,
+    pre_update: fn(&Arc<RwLock<Galaxy>>),
+    post_update: fn(&Arc<RwLock<Galaxy>>),
+) -> thread::JoinHandle<Exit> {
+    time::time_maybe_insert(&galaxy.read());
+    thread::spawn(move || loop {
+        pre_update(&galaxy);
+
+        {
+            let galaxy = galaxy.read();
+            schedule.run(&galaxy);
+        }
+        {
+            let mut galaxy = galaxy.write();
+            if galaxy.update().is_none() {
+                return galaxy.get_exit().unwrap_or_default();
+            }
+            time::time_tick(&galaxy);
+        }
+
+        post_update(&galaxy);
+    })
+}
+
+/// Block the current thread, driving `galaxy` with `stages`.
+/// [`Stage::Startup`] runs once, followed by an update.
+/// Then, each frame runs the rest of the stages in order, updating where requested and at the
+/// end of the frame.
+/// Each stage's exclusive systems run right after its other systems, with `galaxy` borrowed
+/// mutably.
+/// Once exiting, [`Stage::Shutdown`] runs once and the [`Exit`] is returned.
+pub fn run_stages(mut galaxy: Galaxy, mut stages: Stages) -> Exit {
+    time::time_maybe_insert(&galaxy);
+    if run_startup(&mut galaxy, &mut stages).is_some() {
+        while {
+            let live = stages.begin_frame(&galaxy);
+            run_frame(&mut galaxy, &mut stages, live).is_some()
+        } {}
+    }
+    run_shutdown(&mut galaxy, &mut stages)
+}
+
+/// Spawn a new thread and drive `galaxy` the same way as [`self::run_stages`].
+/// `pre_update` and `post_update` are called once per frame, not once per stage.
+/// They behave the same as in [`self::run_spawn_locked`].
+pub fn run_spawn_stages(
+    galaxy: Arc<RwLock<Galaxy>>,
+    mut stages: Stages,
+    pre_update: fn(&Arc<RwLock<Galaxy>>),
+    post_update: fn(&Arc<RwLock<Galaxy>>),
+) -> thread::JoinHandle<Exit> {
+    time::time_maybe_insert(&galaxy.read());
+    thread::spawn(move || {
+        if run_spawn_startup(&galaxy, &mut stages).is_some() {
+            loop {
+                pre_update(&galaxy);
+                let live = stages.begin_frame(&galaxy.read());
+                if run_spawn_frame(&galaxy, &mut stages, live).is_none() {
+                    break;
+                }
+                post_update(&galaxy);
+            }
+        }
+        run_shutdown(&mut galaxy.write(), &mut stages)
+    })
+}
+
+/// Block the current thread, driving `galaxy` with `fixed` at a fixed rate and `stages` once
+/// per frame.
+/// Each frame, `fixed` is run as many times as needed to catch up with [`Time::delta`] (at most
+/// `timestep`'s max steps), updating `galaxy` after each step.
+/// Fixed step updates don't touch events, so events sent during a frame are still seen by the next
+/// frame, and events sent by `fixed` are seen once the frame updates.
+/// This means that fixed steps follow [`Time`]'s scale and stop while paused.
+/// Then, [`FixedTime`] is inserted so `stages` can interpolate between steps.
+///
+/// [`Stage::Startup`] runs once before any fixed step, and [`Stage::Shutdown`] runs once after
+/// exiting.
+pub fn run_fixed(
+    mut galaxy: Galaxy,
+    mut fixed: Schedule,
+    mut stages: Stages,
+    timestep: FixedTimestep,
+) -> Exit {
+    time::time_maybe_insert(&galaxy);
+    let mut accumulator = fixed::FixedAccumulator::new(timestep);
+    if run_startup(&mut galaxy, &mut stages).is_some() {
+        'frames: loop {
+            let live = stages.begin_frame(&galaxy);
+            let steps = if live {
+                accumulator.advance(time::time_delta(&galaxy))
+            } else {
+                0
+            };
+            for _ in 0..steps {
+                fixed.run(&galaxy);
+                if galaxy.update_fixed().is_none() {
+                    break 'frames;
+                }
+            }
+
+            fixed::insert_fixed_time(&galaxy, accumulator.get_fixed_time());
+            if run_frame(&mut galaxy, &mut stages, live).is_none() {
+                break;
+            }
+        }
+    }
+    run_shutdown(&mut galaxy, &mut stages)
+}
+
+/// Spawn a new thread and drive `galaxy` the same way as [`self::run_fixed`].
+/// `pre_update` and `post_update` are called once per frame, not once per fixed step.
+/// They behave the same as in [`self::run_spawn_locked`].
+pub fn run_spawn_fixed(
+    galaxy: Arc<RwLock<Galaxy>>,
+    mut fixed: Schedule,
+    mut stages: Stages,
+    timestep: FixedTimestep,
+    pre_update: fn(&Arc<RwLock<Galaxy>>),
+    post_update: fn(&Arc<RwLock<Galaxy>>),
+) -> thread::JoinHandle<Exit> {
+    time::time_maybe_insert(&galaxy.read());
+    thread::spawn(move || {
+        let mut accumulator = fixed::FixedAccumulator::new(timestep);
+        if run_spawn_startup(&galaxy, &mut stages).is_some() {
+            'frames: loop {
+                pre_update(&galaxy);
+
+                let live = stages.begin_frame(&galaxy.read());
+                let steps = if live {
+                    accumulator.advance(time::time_delta(&galaxy.read()))
+                } else {
+                    0
+                };
+                for _ in 0..steps {
+                    {
+                        let galaxy = galaxy.read();
+                        fixed.run(&galaxy);
+                    }
+                    if galaxy.write().update_fixed().is_none() {
+                        break 'frames;
+                    }
+                }
+
+                fixed::insert_fixed_time(&galaxy.read(), accumulator.get_fixed_time());
+                if run_spawn_frame(&galaxy, &mut stages, live).is_none() {
+                    break;
+                }
+
+                post_update(&galaxy);
+            }
+        }
+        run_shutdown(&mut galaxy.write(), &mut stages)
+    })
+}
+
+fn run_startup(galaxy: &mut Galaxy, stages: &mut Stages) -> Option<()> {
+    stages.run_setup(galaxy);
+    stages.run(Stage::Startup, galaxy);
+    stages.run_exclusive(Stage::Startup, galaxy);
+    galaxy.update()
+}
+
+//  Frames that aren't `live` are paused, so only systems that run while paused run.
+//  Paused frames only update if one of those systems ran, but still stop on exit.
+fn run_frame(galaxy: &mut Galaxy, stages: &mut Stages, live: bool) -> Option<()> {
+    let mut ran = live;
+    for stage in Stage::FRAME {
+        if live {
+            stages.run(stage, galaxy);
+            stages.run_exclusive(stage, galaxy);
+        } else {
+            ran |= stages.run_paused(stage, galaxy);
+        }
+        if stages.updates_after(stage) && ran {
+            galaxy.update()?;
+            ran = live;
+        }
+    }
+    if galaxy.get_exit().is_some() {
+        None?
+    }
+    if live {
+        time::time_tick(galaxy);
+    } else {
+        time::time_tick_frozen(galaxy);
+    }
+    Some(())
+}
+
+//  The galaxy is exiting, so there's no update afterwards.
+fn run_shutdown(galaxy: &mut Galaxy, stages: &mut Stages) -> Exit {
+    stages.run(Stage::Shutdown, galaxy);
+    stages.run_exclusive(Stage::Shutdown, galaxy);
+    galaxy.get_exit().unwrap_or_default()
+}
+
+fn run_spawn_startup(galaxy: &Arc<RwLock<Galaxy>>, stages: &mut Stages) -> Option<()> {
+    stages.run_setup(&galaxy.read());
+    stages.run(Stage::Startup, &galaxy.read());
+    let mut galaxy = galaxy.write();
+    stages.run_exclusive(Stage::Startup, &mut galaxy);
+    galaxy.update()
+}
+
+fn run_spawn_frame(galaxy: &Arc<RwLock<Galaxy>>, stages: &mut Stages, live: bool) -> Option<()> {
+    let mut ran = live;
+    for stage in Stage::FRAME {
+        if live {
+            stages.run(stage, &galaxy.read());
+            //  Only write lock when needed so that other threads aren't held up.
+            if stages.has_exclusive(stage) {
+                stages.run_exclusive(stage, &mut galaxy.write());
+            }
+        } else {
+            ran |= stages.run_paused(stage, &galaxy.read());
+        }
+        if stages.updates_after(stage) && ran {
+            galaxy.write().update()?;
+            ran = live;
+        }
+    }
+    if galaxy.read().get_exit().is_some() {
+        None?
+    }
+    if live {
+        time::time_tick(&galaxy.read());
+    } else {
+        time::time_tick_frozen(&galaxy.read());
+    }
+    Some(())
+}
@@ -0,0 +1,420 @@
+use super::*;
+use crate::{
+    ecs::{QueryLockType, Result},
+    galaxy::{
+        ComponentAccessesNormal, ComponentAccessesOptional, ComponentAccessesReadOnly,
+        QueryCombinations, QueryEIter, QueryGet, QueryInfo, QueryIter,
+    },
+};
+use std::{
+    any::TypeId,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+/// Something that a system can take as an argument in place of `&Galaxy`.
+/// Accesses are known before the system ever runs, so [`System::new`] declares them for you.
+pub trait SystemParam {
+    /// Persistent state owned by the system, see [`Local`].
+    type State: Send + 'static;
+    type Item<'g, 's>;
+
+    fn init() -> Self::State;
+    fn access(access: &mut SystemAccess);
+    fn get<'g, 's>(galaxy: &'g Galaxy, state: &'s mut Self::State) -> Self::Item<'g, 's>;
+}
+
+/// Something that can be turned into a [`System`], mostly functions whose arguments are all
+/// [`SystemParam`]s.
+/// `P` only exists to keep the implementations apart.
+pub trait IntoSystem<P> {
+    fn into_system(self) -> System;
+}
+
+//  Also allows mixing `&Galaxy` with other parameters.
+//  Anything could be read through it, so it declares a read of everything.
+//  Writes through it still have to be declared.
+impl SystemParam for &Galaxy {
+    type State = ();
+    type Item<'g, 's> = &'g Galaxy;
+
+    fn init() -> Self::State {}
+
+    fn access(access: &mut SystemAccess) {
+        access.insert_read_all();
+    }
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, _state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        galaxy
+    }
+}
+
+/// Queries `CA`, the same as [`Galaxy::query`].
+/// [`Query::changed`] and [`Query::added`] are relative to this system's last run.
+pub struct Query<'g, CA> {
+    galaxy: &'g Galaxy,
+    last_run: u64,
+    this_run: u64,
+    phantom: PhantomData<CA>,
+}
+
+impl<'g, CA> Query<'g, CA>
+where
+    CA: ComponentAccessesOptional,
+{
+    pub fn with<CF: ComponentAccessesNormal>(&self) -> QueryInfo<'g, CA> {
+        self.info().with::<CF>()
+    }
+
+    pub fn without<CF: ComponentAccessesNormal>(&self) -> QueryInfo<'g, CA> {
+        self.info().without::<CF>()
+    }
+
+    pub fn or<CF: ComponentAccessesNormal>(&self) -> QueryInfo<'g, CA> {
+        self.info().or::<CF>()
+    }
+
+    /// See [`QueryInfo::changed`].
+    pub fn changed<CF: ComponentAccessesNormal>(&self) -> QueryInfo<'g, CA> {
+        self.info().changed::<CF>()
+    }
+
+    /// See [`QueryInfo::added`].
+    pub fn added<CF: ComponentAccessesNormal>(&self) -> QueryInfo<'g, CA> {
+        self.info().added::<CF>()
+    }
+
+    pub fn iter(&self) -> QueryIter<'g, CA> {
+        self.info().iter()
+    }
+
+    pub fn eiter(&self) -> QueryEIter<'g, CA> {
+        self.info().eiter()
+    }
+
+    /// See [`QueryInfo::get`].
+    pub fn get(&self, entity: Entity) -> Option<QueryGet<'g, CA>> {
+        self.info().get(entity)
+    }
+
+    /// See [`QueryInfo::try_iter`].
+    pub fn try_iter(&self) -> Result<QueryIter<'g, CA>> {
+        self.info().try_iter()
+    }
+
+    /// See [`QueryInfo::try_get`].
+    pub fn try_get(&self, entity: Entity) -> Result<Option<QueryGet<'g, CA>>> {
+        self.info().try_get(entity)
+    }
+
+    /// See [`QueryInfo::iter_combinations`].
+    pub fn iter_combinations<const K: usize>(&self) -> QueryCombinations<'g, CA, K>
+    where
+        CA: ComponentAccessesReadOnly,
+    {
+        self.info().iter_combinations()
+    }
+
+    /// See [`QueryInfo::for_each_combination`].
+    pub fn for_each_combination<const K: usize, F>(&self, f: F)
+    where
+        F: for<'a> FnMut([CA::Item<'a>; K]),
+    {
+        self.info().for_each_combination(f)
+    }
+
+    /// See [`QueryInfo::count`].
+    pub fn count(&self) -> usize {
+        self.info().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.info().is_empty()
+    }
+
+    /// See [`QueryInfo::get_single`].
+    pub fn get_single(&self) -> Result<QueryGet<'g, CA>> {
+        self.info().get_single()
+    }
+
+    /// See [`QueryInfo::single`].
+    pub fn single(&self) -> QueryGet<'g, CA> {
+        self.info().single()
+    }
+
+    /// See [`QueryInfo::par_for_each`].
+    pub fn par_for_each<F>(&self, batch_size: usize, f: F)
+    where
+        CA: Send,
+        F: Fn(CA) + Send + Sync,
+    {
+        self.info().par_for_each(batch_size, f)
+    }
+
+    fn info(&self) -> QueryInfo<'g, CA> {
+        self.galaxy.query_ticked::<CA>(self.last_run, self.this_run)
+    }
+}
+
+//  The state is the tick of the last run.
+impl<'a, CA> SystemParam for Query<'a, CA>
+where
+    CA: ComponentAccessesOptional,
+{
+    type State = u64;
+    type Item<'g, 's> = Query<'g, CA>;
+
+    fn init() -> Self::State {
+        0
+    }
+
+    fn access(access: &mut SystemAccess) {
+        for (cty, info) in CA::infos() {
+            access.insert_component(cty, info);
+        }
+    }
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        let this_run = galaxy.next_change_tick();
+        Query {
+            galaxy,
+            last_run: std::mem::replace(state, this_run),
+            this_run,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Reads the single resource `R`.
+/// Panics if `R` doesn't exist, use `Option<Res<R>>` otherwise.
+pub struct Res<'g, R: Resource + 'static>(ResourceReadGuard<'g, R>);
+
+impl<'g, R: Resource + 'static> Deref for Res<'g, R> {
+    type Target = R;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, R: Resource + 'static> SystemParam for Res<'a, R> {
+    type State = ();
+    type Item<'g, 's> = Res<'g, R>;
+
+    fn init() -> Self::State {}
+
+    fn access(access: &mut SystemAccess) {
+        access.insert_resource(R::mewo_resource_type_id(), QueryLockType::Read);
+    }
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        <Option<Res<R>> as SystemParam>::get(galaxy, state)
+            .unwrap_or_else(|| panic!("Resource `{}` does not exist", std::any::type_name::<R>()))
+    }
+}
+
+impl<'a, R: Resource + 'static> SystemParam for Option<Res<'a, R>> {
+    type State = ();
+    type Item<'g, 's> = Option<Res<'g, R>>;
+
+    fn init() -> Self::State {}
+
+    fn access(access: &mut SystemAccess) {
+        <Res<R> as SystemParam>::access(access)
+    }
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, _state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        if !galaxy.has_resource::<R, _>(TypeId::of::<R>()) {
+            None?
+        }
+        galaxy.get_resource::<R, _>(TypeId::of::<R>()).map(Res)
+    }
+}
+
+/// Writes the single resource `R`.
+/// Panics if `R` doesn't exist, use `Option<ResMut<R>>` otherwise.
+pub struct ResMut<'g, R: Resource + 'static>(ResourceWriteGuard<'g, R>);
+
+impl<'g, R: Resource + 'static> Deref for ResMut<'g, R> {
+    type Target = R;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'g, R: Resource + 'static> DerefMut for ResMut<'g, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a, R: Resource + 'static> SystemParam for ResMut<'a, R> {
+    type State = ();
+    type Item<'g, 's> = ResMut<'g, R>;
+
+    fn init() -> Self::State {}
+
+    fn access(access: &mut SystemAccess) {
+        access.insert_resource(R::mewo_resource_type_id(), QueryLockType::Write);
+    }
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        <Option<ResMut<R>> as SystemParam>::get(galaxy, state)
+            .unwrap_or_else(|| panic!("Resource `{}` does not exist", std::any::type_name::<R>()))
+    }
+}
+
+impl<'a, R: Resource + 'static> SystemParam for Option<ResMut<'a, R>> {
+    type State = ();
+    type Item<'g, 's> = Option<ResMut<'g, R>>;
+
+    fn init() -> Self::State {}
+
+    fn access(access: &mut SystemAccess) {
+        <ResMut<R> as SystemParam>::access(access)
+    }
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, _state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        if !galaxy.has_resource::<R, _>(TypeId::of::<R>()) {
+            None?
+        }
+        galaxy
+            .get_mut_resource::<R, _>(TypeId::of::<R>())
+            .map(ResMut)
+    }
+}
+
+/// The events of type `E` inserted last frame.
+pub struct Events<'g, E>(&'g [E]);
+
+impl<'g, E> Deref for Events<'g, E> {
+    type Target = [E];
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+//  Events are only read here, and inserting is thread local, so there's nothing to declare.
+impl<'a, E: Event + 'static> SystemParam for Events<'a, E> {
+    type State = ();
+    type Item<'g, 's> = Events<'g, E>;
+
+    fn init() -> Self::State {}
+
+    fn access(_access: &mut SystemAccess) {}
+
+    fn get<'g, 's>(galaxy: &'g Galaxy, _state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        Events(galaxy.get_events::<E>())
+    }
+}
+
+/// State owned by a single system that persists between frames, starting from
+/// `T::default()`.
+/// No other system, not even another system wrapping the same function, can see it.
+pub struct Local<'s, T>(&'s mut T);
+
+impl<'s, T> Deref for Local<'s, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'s, T> DerefMut for Local<'s, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T: Default + Send + 'static> SystemParam for Local<'a, T> {
+    type State = T;
+    type Item<'g, 's> = Local<'s, T>;
+
+    fn init() -> Self::State {
+        T::default()
+    }
+
+    fn access(_access: &mut SystemAccess) {}
+
+    fn get<'g, 's>(_galaxy: &'g Galaxy, state: &'s mut Self::State) -> Self::Item<'g, 's> {
+        Local(state)
+    }
+}
+
+/// Turn `func` into a plain `&Galaxy` system that receives its own [`Local`] state.
+/// This is useful for the runners that don't take [`System`]s.
+///
+/// ```rust,ignore
+/// fn count_frames(g: &Galaxy, mut frames: Local<usize>) {
+///     *frames += 1;
+/// }
+///
+/// run_single(galaxy, vec![Box::new(with_local(count_frames))]);
+/// ```
+pub fn with_local<T, F>(mut func: F) -> impl FnMut(&Galaxy) + Send + 'static
+where
+    T: Default + Send + 'static,
+    F: FnMut(&Galaxy, Local<T>) + Send + 'static,
+{
+    let mut state = T::default();
+    move |galaxy| func(galaxy, Local(&mut state))
+}
+
+impl IntoSystem<()> for System {
+    fn into_system(self) -> System {
+        self
+    }
+}
+
+macro_rules! impl_into_system {
+    ($($p:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<F, $($p),*> IntoSystem<fn($($p),*)> for F
+        where
+            F: Send + 'static,
+            for<'a> &'a mut F: FnMut($($p),*),
+            for<'a, 'g, 's> &'a mut F: FnMut($($p::Item<'g, 's>),*),
+            $($p: SystemParam + 'static),*
+        {
+            fn into_system(mut self) -> System {
+                //  Forces rustc to pick the `SystemParam::Item` implementation of `FnMut`.
+                //  Bounding `&mut F` rather than `F` also lets opaque `impl FnMut` types through.
+                #[allow(clippy::too_many_arguments)]
+                fn call<$($p),*>(mut func: impl FnMut($($p),*), $($p: $p),*) {
+                    func($($p),*)
+                }
+
+                //  Parameters that conflict would wait on each other's locks forever.
+                let mut access = SystemAccess::new();
+                $(
+                    let mut param = SystemAccess::new();
+                    $p::access(&mut param);
+                    assert!(
+                        access.is_declared_compatible(&param),
+                        "System `{}` has a parameter `{}` that conflicts with another parameter",
+                        std::any::type_name::<F>(),
+                        std::any::type_name::<$p>(),
+                    );
+                    access.extend(param);
+                )*
+                let mut state = ($($p::init(),)*);
+                System::from_parts(
+                    Box::new(move |galaxy: &Galaxy| {
+                        let ($($p,)*) = &mut state;
+                        call(&mut self, $($p::get(galaxy, $p)),*);
+                    }),
+                    std::any::type_name::<F>(),
+                    access,
+                )
+            }
+        }
+    };
+}
+
+impl_into_system!(P0);
+impl_into_system!(P0, P1);
+impl_into_system!(P0, P1, P2);
+impl_into_system!(P0, P1, P2, P3);
+impl_into_system!(P0, P1, P2, P3, P4);
+impl_into_system!(P0, P1, P2, P3, P4, P5);
+impl_into_system!(P0, P1, P2, P3, P4, P5, P6);
+impl_into_system!(P0, P1, P2, P3, P4, P5, P6, P7);
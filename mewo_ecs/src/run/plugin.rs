@@ -0,0 +1,61 @@
+use super::*;
+use crate::{
+    ecs::{ECSError, ErrorType, Result},
+    ecs_err,
+};
+
+/// Bundles resources, events, components and systems so they can be added to a
+/// [`StagesBuilder`] in one call with [`StagesBuilder::add_plugin`].
+///
+/// ```rust,ignore
+/// struct PhysicsPlugin;
+///
+/// impl Plugin for PhysicsPlugin {
+///     fn dependencies() -> Vec<&'static str> {
+///         vec![TimePlugin::name()]
+///     }
+///
+///     fn build(&self, stages: StagesBuilder) -> StagesBuilder {
+///         stages
+///             .insert_resource(Gravity::single_resource(), Gravity(9.8))
+///             .register_event::<CollisionEvent>()
+///             .insert(Stage::Update, System::new(physics_step))
+///     }
+/// }
+/// ```
+pub trait Plugin {
+    fn name() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>()
+    }
+
+    /// The names of the plugins that must also be added, in any order.
+    fn dependencies() -> Vec<&'static str>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    fn build(&self, stages: StagesBuilder) -> StagesBuilder;
+}
+
+//  Each plugin's name along with its dependencies.
+pub(super) fn check_plugins(plugins: &[(&'static str, Vec<&'static str>)]) -> Result<()> {
+    for (idx, (plugin, dependencies)) in plugins.iter().enumerate() {
+        if plugins[..idx].iter().any(|(other, _)| other == plugin) {
+            Err(ecs_err!(ErrorType::PluginDuplicate { plugin }, plugins))?
+        }
+        for dependency in dependencies.iter() {
+            if !plugins.iter().any(|(other, _)| other == dependency) {
+                Err(ecs_err!(
+                    ErrorType::PluginDependencyMissing { plugin, dependency },
+                    plugins
+                ))?
+            }
+        }
+    }
+    Ok(())
+}
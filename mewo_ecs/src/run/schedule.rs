@@ -0,0 +1,295 @@
+use super::*;
+use crate::{
+    data::ThreadPool,
+    ecs::{ECSError, ErrorType, Result},
+    ecs_err,
+};
+use std::{cmp::Reverse, collections::BinaryHeap, sync::mpsc};
+
+/// Collects [`System`]s to be run in parallel.
+pub struct ScheduleBuilder {
+    systems: Vec<System>,
+    sets: Vec<(&'static str, Condition)>,
+}
+
+impl ScheduleBuilder {
+    pub fn new() -> Self {
+        ScheduleBuilder {
+            systems: Vec::new(),
+            sets: Vec::new(),
+        }
+    }
+
+    pub fn insert(mut self, system: System) -> Self {
+        self.systems.push(system);
+        self
+    }
+
+    /// Only run systems labeled `label` when `condition` is true.
+    /// The condition is evaluated once per frame no matter how many systems share the label.
+    pub fn set_run_if(mut self, label: &'static str, condition: Condition) -> Self {
+        if let Some(idx) = self.sets.iter().position(|(set, _)| *set == label) {
+            let (_, old) = self.sets.remove(idx);
+            self.sets.insert(idx, (label, old.and(condition)));
+        } else {
+            self.sets.push((label, condition));
+        }
+        self
+    }
+
+    /// Sort systems according to their `before` and `after` constraints.
+    /// Systems without constraints between them keep their insertion order.
+    pub fn build(self) -> Result<Schedule> {
+        let Self { systems, sets } = self;
+
+        //  dependencies[b] contains a when a must complete before b starts.
+        let mut dependencies = vec![Vec::new(); systems.len()];
+        for (idx, system) in systems.iter().enumerate() {
+            for &label in system.get_befores() {
+                for other in labeled(&systems, label)? {
+                    if other != idx {
+                        dependencies[other].push(idx);
+                    }
+                }
+            }
+            for &label in system.get_afters() {
+                for other in labeled(&systems, label)? {
+                    if other != idx {
+                        dependencies[idx].push(other);
+                    }
+                }
+            }
+        }
+
+        let order = topological_sort(&dependencies).map_err(|cycle| {
+            let systems: Vec<String> = cycle
+                .into_iter()
+                .map(|idx| systems[idx].describe())
+                .collect();
+            ecs_err!(
+                ErrorType::ScheduleCycle {
+                    systems: systems.clone()
+                },
+                systems
+            )
+        })?;
+
+        //  Move everything into sorted order.
+        let mut new_idxs = vec![0; systems.len()];
+        for (new_idx, &old_idx) in order.iter().enumerate() {
+            new_idxs[old_idx] = new_idx;
+        }
+        let dependencies = order
+            .iter()
+            .map(|&old_idx| {
+                dependencies[old_idx]
+                    .iter()
+                    .map(|&dep| new_idxs[dep])
+                    .collect()
+            })
+            .collect();
+        let mut systems: Vec<Option<System>> = systems.into_iter().map(Some).collect();
+        let systems: Vec<System> = order
+            .iter()
+            .map(|&old_idx| systems[old_idx].take().unwrap())
+            .collect();
+
+        let conflicts = systems
+            .iter()
+            .map(|a| {
+                systems
+                    .iter()
+                    .map(|b| !a.get_access().is_compatible(b.get_access()))
+                    .collect()
+            })
+            .collect();
+        let mut system_sets = vec![Vec::new(); systems.len()];
+        for (set_idx, (label, _)) in sets.iter().enumerate() {
+            for sys in labeled(&systems, label)? {
+                system_sets[sys].push(set_idx);
+            }
+        }
+        let sets = sets.into_iter().map(|(_, condition)| condition).collect();
+
+        Ok(Schedule {
+            systems,
+            dependencies,
+            conflicts,
+            sets,
+            system_sets,
+        })
+    }
+}
+
+fn labeled(systems: &[System], label: &'static str) -> Result<Vec<usize>> {
+    let labeled: Vec<usize> = systems
+        .iter()
+        .enumerate()
+        .filter(|(_, system)| system.get_labels().contains(&label))
+        .map(|(idx, _)| idx)
+        .collect();
+    if labeled.is_empty() {
+        Err(ecs_err!(ErrorType::ScheduleLabelMissing { label }, label))?
+    }
+    Ok(labeled)
+}
+
+//  Kahn's algorithm, always picking the lowest ready index to keep insertion order stable.
+//  On failure, the systems that form a cycle are returned.
+fn topological_sort(dependencies: &[Vec<usize>]) -> std::result::Result<Vec<usize>, Vec<usize>> {
+    let len = dependencies.len();
+    let mut dependents = vec![Vec::new(); len];
+    let mut remaining: Vec<usize> = dependencies.iter().map(|deps| deps.len()).collect();
+    for (idx, deps) in dependencies.iter().enumerate() {
+        for &dep in deps {
+            dependents[dep].push(idx);
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = remaining
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count == 0)
+        .map(|(idx, _)| Reverse(idx))
+        .collect();
+    let mut order = Vec::with_capacity(len);
+    while let Some(Reverse(idx)) = ready.pop() {
+        order.push(idx);
+        for &dependent in dependents[idx].iter() {
+            remaining[dependent] -= 1;
+            if remaining[dependent] == 0 {
+                ready.push(Reverse(dependent));
+            }
+        }
+    }
+
+    if order.len() == len {
+        return Ok(order);
+    }
+
+    //  Every unsorted system waits on another unsorted system, so walking backwards must
+    //  eventually revisit a system.
+    let mut path = Vec::new();
+    let mut idx = remaining.iter().position(|&count| count != 0).unwrap();
+    loop {
+        if let Some(pos) = path.iter().position(|&visited| visited == idx) {
+            let mut cycle = path.split_off(pos);
+            cycle.reverse();
+            return Err(cycle);
+        }
+        path.push(idx);
+        idx = *dependencies[idx]
+            .iter()
+            .find(|&&dep| remaining[dep] != 0)
+            .unwrap();
+    }
+}
+
+impl Default for ScheduleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A set of systems that runs non-conflicting systems concurrently on the global thread pool.
+/// Systems that conflict with each other still run in their sorted order.
+pub struct Schedule {
+    systems: Vec<System>,
+    dependencies: Vec<Vec<usize>>,
+    //  conflicts[a][b] is true when systems a and b can't run at the same time.
+    conflicts: Vec<Vec<bool>>,
+    sets: Vec<Condition>,
+    //  Indices into `sets` for each system.
+    system_sets: Vec<Vec<usize>>,
+}
+
+impl Schedule {
+    /// Run every system whose conditions pass once, returning when all of them have completed.
+    pub fn run(&mut self, galaxy: &Galaxy) {
+        self.run_filtered(galaxy, false);
+    }
+
+    /// Like [`Schedule::run`], but only systems marked with [`System::run_while_paused`] run.
+    /// Returns whether any system ran.
+    pub fn run_paused(&mut self, galaxy: &Galaxy) -> bool {
+        self.run_filtered(galaxy, true)
+    }
+
+    //  Returns whether any system ran.
+    fn run_filtered(&mut self, galaxy: &Galaxy, paused: bool) -> bool {
+        let pool = ThreadPool::get_global_pool();
+        let (done_sender, done_receiver) = mpsc::channel();
+        let mut pending: Vec<usize> = Vec::new();
+        let mut running: Vec<usize> = Vec::new();
+        let mut done = vec![false; self.systems.len()];
+
+        //  Skipped systems count as done so that their dependents still run.
+        //  Conditions of systems filtered out by `paused` aren't evaluated, so stateful
+        //  conditions don't advance.
+        let mut set_checks: Vec<Option<bool>> = vec![None; self.sets.len()];
+        for (sys, system) in self.systems.iter_mut().enumerate() {
+            if paused && !system.get_run_while_paused() {
+                done[sys] = true;
+                continue;
+            }
+            let set_check = self.system_sets[sys].iter().fold(true, |acc, &set| {
+                *set_checks[set].get_or_insert_with(|| self.sets[set].check(galaxy)) & acc
+            });
+            if system.check(galaxy) & set_check {
+                pending.push(sys);
+            } else {
+                done[sys] = true;
+            }
+        }
+
+        //  Each system is handed out once, so running systems never share their state.
+        let mut funcs: Vec<Option<&mut BoxedSystem>> = self
+            .systems
+            .iter_mut()
+            .map(|system| Some(system.get_func_mut()))
+            .collect();
+
+        let ran = !pending.is_empty();
+        pool.scope(|scope| {
+            while !pending.is_empty() || !running.is_empty() {
+                let mut pending_idx = 0;
+                while pending_idx < pending.len() {
+                    let sys = pending[pending_idx];
+                    //  Don't overtake conflicting systems that were sorted earlier.
+                    let ready = self.dependencies[sys].iter().all(|&dep| done[dep])
+                        && running
+                            .iter()
+                            .chain(pending[..pending_idx].iter())
+                            .all(|&other| !self.conflicts[sys][other]);
+                    if !ready {
+                        pending_idx += 1;
+                        continue;
+                    }
+                    pending.remove(pending_idx);
+                    running.push(sys);
+
+                    let func = funcs[sys].take().unwrap();
+                    let done = SystemDone(sys, done_sender.clone());
+                    scope.spawn(move || {
+                        let _done = done;
+                        func(galaxy);
+                    });
+                }
+
+                let finished = done_receiver.recv().unwrap();
+                running.retain(|&sys| sys != finished);
+                done[finished] = true;
+            }
+        });
+        ran
+    }
+}
+
+//  Reports completion even if the system panics.
+struct SystemDone(usize, mpsc::Sender<usize>);
+
+impl Drop for SystemDone {
+    fn drop(&mut self) {
+        let _ = self.1.send(self.0);
+    }
+}
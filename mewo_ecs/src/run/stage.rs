@@ -0,0 +1,224 @@
+use super::*;
+use crate::ecs::Result;
+use std::hash::Hash;
+
+/// The stages that systems can be inserted into.
+/// `Startup` runs once before the first frame, and `Shutdown` runs once after exiting.
+/// The rest run every frame in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Startup,
+    PreUpdate,
+    Update,
+    PostUpdate,
+    Last,
+    Shutdown,
+}
+
+impl Stage {
+    pub const FRAME: [Stage; 4] = [
+        Stage::PreUpdate,
+        Stage::Update,
+        Stage::PostUpdate,
+        Stage::Last,
+    ];
+
+    fn idx(self) -> usize {
+        self as usize
+    }
+
+    fn count() -> usize {
+        Stage::Shutdown.idx() + 1
+    }
+}
+
+/// A system with mutable access to the galaxy, see [`StagesBuilder::insert_exclusive`].
+pub type BoxedExclusiveSystem = Box<dyn FnMut(&mut Galaxy) + Send>;
+
+//  Runs once before `Startup`.
+type BoxedSetup = Box<dyn FnOnce(&Galaxy) + Send>;
+
+/// Collects [`System`]s into [`Stage`]s.
+/// Ordering with `before` and `after` only applies to systems within the same stage.
+pub struct StagesBuilder {
+    stages: Vec<ScheduleBuilder>,
+    exclusives: Vec<Vec<BoxedExclusiveSystem>>,
+    updates: Vec<bool>,
+    setups: Vec<BoxedSetup>,
+    plugins: Vec<(&'static str, Vec<&'static str>)>,
+    control: Option<RunControl>,
+}
+
+impl StagesBuilder {
+    pub fn new() -> Self {
+        StagesBuilder {
+            stages: (0..Stage::count())
+                .map(|_| ScheduleBuilder::new())
+                .collect(),
+            exclusives: (0..Stage::count()).map(|_| Vec::new()).collect(),
+            updates: (0..Stage::count())
+                .map(|idx| idx == Stage::Startup.idx())
+                .collect(),
+            setups: Vec::new(),
+            plugins: Vec::new(),
+            control: None,
+        }
+    }
+
+    /// Let `control` pause, resume and step the runner.
+    pub fn control(mut self, control: RunControl) -> Self {
+        self = self.insert_resource(RunControl::single_resource(), control.clone());
+        self.control = Some(control);
+        self
+    }
+
+    /// Add everything in `plugin`.
+    /// Missing dependencies and plugins added twice are reported by [`StagesBuilder::build`].
+    pub fn add_plugin<P: Plugin>(mut self, plugin: P) -> Self {
+        self.plugins.push((P::name(), P::dependencies()));
+        plugin.build(self)
+    }
+
+    /// Insert `r` before [`Stage::Startup`] runs.
+    pub fn insert_resource<R, RH>(mut self, rh: RH, r: R) -> Self
+    where
+        R: Resource + Send + 'static,
+        RH: Clone + Hash + Send + 'static,
+    {
+        self.setups.push(Box::new(move |galaxy| {
+            galaxy.insert_resource(rh, r);
+        }));
+        self
+    }
+
+    /// Register `E` before [`Stage::Startup`] runs.
+    pub fn register_event<E: Event + 'static>(mut self) -> Self {
+        self.setups.push(Box::new(|galaxy| {
+            galaxy.register_event::<E>();
+        }));
+        self
+    }
+
+    /// Register `C` before [`Stage::Startup`] runs.
+    pub fn register_component<C: GenericComponent + 'static>(mut self) -> Self {
+        self.setups.push(Box::new(|galaxy| {
+            galaxy.register_component::<C>();
+        }));
+        self
+    }
+
+    pub fn insert(mut self, stage: Stage, system: System) -> Self {
+        self.map_stage(stage, |builder| builder.insert(system));
+        self
+    }
+
+    /// Run `system` at the end of `stage` once every other system in `stage` has completed.
+    /// No other system runs alongside it, and it runs before the update after `stage`, if any.
+    /// Exclusive systems in the same stage run in insertion order.
+    pub fn insert_exclusive<F>(mut self, stage: Stage, system: F) -> Self
+    where
+        F: FnMut(&mut Galaxy) + Send + 'static,
+    {
+        self.exclusives[stage.idx()].push(Box::new(system));
+        self
+    }
+
+    /// See [`ScheduleBuilder::set_run_if`].
+    pub fn set_run_if(mut self, stage: Stage, label: &'static str, condition: Condition) -> Self {
+        self.map_stage(stage, |builder| builder.set_run_if(label, condition));
+        self
+    }
+
+    /// Update the galaxy after `stage` so that later stages see its entities, components and
+    /// events.
+    /// `Startup` always updates, and so does the end of each frame.
+    pub fn update_after(mut self, stage: Stage) -> Self {
+        self.updates[stage.idx()] = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Stages> {
+        plugin::check_plugins(&self.plugins)?;
+        Ok(Stages {
+            stages: self
+                .stages
+                .into_iter()
+                .map(ScheduleBuilder::build)
+                .collect::<Result<_>>()?,
+            exclusives: self.exclusives,
+            updates: self.updates,
+            setups: self.setups,
+            control: self.control,
+        })
+    }
+
+    fn map_stage<F: FnOnce(ScheduleBuilder) -> ScheduleBuilder>(&mut self, stage: Stage, f: F) {
+        let builder = std::mem::take(&mut self.stages[stage.idx()]);
+        self.stages[stage.idx()] = f(builder);
+    }
+}
+
+impl Default for StagesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`Schedule`] for each [`Stage`].
+pub struct Stages {
+    stages: Vec<Schedule>,
+    exclusives: Vec<Vec<BoxedExclusiveSystem>>,
+    updates: Vec<bool>,
+    setups: Vec<BoxedSetup>,
+    control: Option<RunControl>,
+}
+
+impl Stages {
+    /// Insert resources and register types from the builder.
+    /// Only the first call does anything.
+    pub fn run_setup(&mut self, galaxy: &Galaxy) {
+        self.setups.drain(..).for_each(|setup| setup(galaxy))
+    }
+
+    pub fn run(&mut self, stage: Stage, galaxy: &Galaxy) {
+        self.stages[stage.idx()].run(galaxy)
+    }
+
+    /// See [`Schedule::run_paused`].
+    pub fn run_paused(&mut self, stage: Stage, galaxy: &Galaxy) -> bool {
+        self.stages[stage.idx()].run_paused(galaxy)
+    }
+
+    /// Call at the start of each frame.
+    /// Returns false when the frame is paused by the [`RunControl`], if any.
+    /// While paused, this blocks until the control changes or [`RunControl::PAUSED_FRAME`] passes.
+    pub fn begin_frame(&mut self, galaxy: &Galaxy) -> bool {
+        let control = match self.control.as_ref() {
+            Some(control) => control,
+            None => return true,
+        };
+        loop {
+            if control.advance(galaxy) {
+                return true;
+            }
+            if !control.wait_paused(RunControl::PAUSED_FRAME) {
+                return false;
+            }
+        }
+    }
+
+    pub fn run_exclusive(&mut self, stage: Stage, galaxy: &mut Galaxy) {
+        self.exclusives[stage.idx()]
+            .iter_mut()
+            .for_each(|sys| sys(galaxy))
+    }
+
+    pub fn has_exclusive(&self, stage: Stage) -> bool {
+        !self.exclusives[stage.idx()].is_empty()
+    }
+
+    /// Whether the galaxy should be updated after `stage`.
+    pub fn updates_after(&self, stage: Stage) -> bool {
+        stage == Stage::Last || self.updates[stage.idx()]
+    }
+}
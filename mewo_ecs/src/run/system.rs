@@ -0,0 +1,215 @@
+use super::*;
+use crate::{
+    ecs::{ComponentTypeId, QueryAccessType, QueryLockType, ResourceTypeId},
+    galaxy::ComponentAccessesOptional,
+};
+
+/// A type erased system, accepted by the runners.
+pub type BoxedSystem = Box<dyn FnMut(&Galaxy) + Send>;
+
+//  Which components and resources a system touches and how.
+//  Two systems whose accesses are compatible never contend for the same storage locks.
+//  `reads_all` is for `&Galaxy`, which could read anything.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAccess {
+    components: Vec<(ComponentTypeId, QueryLockType)>,
+    resources: Vec<(ResourceTypeId, QueryLockType)>,
+    reads_all: bool,
+}
+
+impl SystemAccess {
+    pub fn new() -> Self {
+        SystemAccess {
+            components: Vec::new(),
+            resources: Vec::new(),
+            reads_all: false,
+        }
+    }
+
+    pub fn insert_component(&mut self, cty: ComponentTypeId, access: QueryAccessType) {
+        self.components.push((cty, access.into_lock()));
+    }
+
+    pub fn insert_resource(&mut self, rty: ResourceTypeId, lock: QueryLockType) {
+        self.resources.push((rty, lock));
+    }
+
+    /// Declare a read of every component and resource.
+    pub fn insert_read_all(&mut self) {
+        self.reads_all = true;
+    }
+
+    pub(super) fn extend(&mut self, other: SystemAccess) {
+        self.components.extend(other.components);
+        self.resources.extend(other.resources);
+        self.reads_all |= other.reads_all;
+    }
+
+    pub fn is_compatible(&self, other: &SystemAccess) -> bool {
+        self.is_declared_compatible(other)
+            && !(self.reads_all && other.has_write())
+            && !(other.reads_all && self.has_write())
+    }
+
+    //  Only compares what was declared one by one, ignoring `reads_all`.
+    //  A system's parameters are checked against each other with this, since `&Galaxy` would
+    //  otherwise clash with every `ResMut` or `Query<&mut C>` beside it.
+    pub(super) fn is_declared_compatible(&self, other: &SystemAccess) -> bool {
+        fn compatible<T: PartialEq>(a: &[(T, QueryLockType)], b: &[(T, QueryLockType)]) -> bool {
+            a.iter().all(|(a_id, a_lock)| {
+                b.iter().all(|(b_id, b_lock)| {
+                    a_id != b_id
+                        || matches!((a_lock, b_lock), (QueryLockType::Read, QueryLockType::Read))
+                })
+            })
+        }
+        compatible(&self.components, &other.components)
+            && compatible(&self.resources, &other.resources)
+    }
+
+    fn has_write(&self) -> bool {
+        self.components
+            .iter()
+            .map(|(_, lock)| lock)
+            .chain(self.resources.iter().map(|(_, lock)| lock))
+            .any(|lock| matches!(lock, QueryLockType::Write))
+    }
+}
+
+/// A system along with the components and resources that it accesses.
+/// Systems that take `&Galaxy` are assumed to read everything, so they never run alongside a
+/// system that writes.
+/// Writes through `&Galaxy` are not checked though, so a system that queries `&mut Player`
+/// without declaring it may run alongside another system that reads `Player`.
+pub struct System {
+    func: BoxedSystem,
+    name: &'static str,
+    access: SystemAccess,
+    labels: Vec<&'static str>,
+    befores: Vec<&'static str>,
+    afters: Vec<&'static str>,
+    conditions: Vec<Condition>,
+    while_paused: bool,
+}
+
+impl System {
+    /// Create a system from a function or closure taking `&Galaxy` or any number of
+    /// [`SystemParam`]s.
+    /// The accesses of each parameter are declared automatically.
+    pub fn new<P, F: IntoSystem<P>>(func: F) -> Self {
+        func.into_system()
+    }
+
+    pub(super) fn from_parts(func: BoxedSystem, name: &'static str, access: SystemAccess) -> Self {
+        System {
+            func,
+            name,
+            access,
+            labels: Vec::new(),
+            befores: Vec::new(),
+            afters: Vec::new(),
+            conditions: Vec::new(),
+            while_paused: false,
+        }
+    }
+
+    /// Only run this system when `condition` is true.
+    /// Multiple conditions must all be true.
+    pub fn run_if(mut self, condition: Condition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Keep running this system while paused by a [`RunControl`], for example, to keep
+    /// rendering or to handle the key that resumes.
+    pub fn run_while_paused(mut self) -> Self {
+        self.while_paused = true;
+        self
+    }
+
+    /// Tag this system with `label`.
+    /// Many systems can share one label, and one system can have many labels.
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Run this system before every system labeled `label`.
+    pub fn before(mut self, label: &'static str) -> Self {
+        self.befores.push(label);
+        self
+    }
+
+    /// Run this system after every system labeled `label`.
+    pub fn after(mut self, label: &'static str) -> Self {
+        self.afters.push(label);
+        self
+    }
+
+    /// Declare the components this system queries, using the same syntax as
+    /// [`Galaxy::query`](crate::Galaxy::query).
+    pub fn access<CA: ComponentAccessesOptional>(mut self) -> Self {
+        for (cty, access) in CA::infos() {
+            self.access.insert_component(cty, access);
+        }
+        self
+    }
+
+    /// Declare that this system reads the resource `R`.
+    pub fn resource<R: Resource + 'static>(mut self) -> Self {
+        self.access
+            .insert_resource(R::mewo_resource_type_id(), QueryLockType::Read);
+        self
+    }
+
+    /// Declare that this system writes the resource `R`.
+    pub fn resource_mut<R: Resource + 'static>(mut self) -> Self {
+        self.access
+            .insert_resource(R::mewo_resource_type_id(), QueryLockType::Write);
+        self
+    }
+
+    pub fn get_access(&self) -> &SystemAccess {
+        &self.access
+    }
+
+    pub fn get_labels(&self) -> &[&'static str] {
+        &self.labels
+    }
+
+    /// The type name of the function or closure, used in errors.
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub(super) fn get_func_mut(&mut self) -> &mut BoxedSystem {
+        &mut self.func
+    }
+
+    pub(super) fn get_run_while_paused(&self) -> bool {
+        self.while_paused
+    }
+
+    pub(super) fn get_befores(&self) -> &[&'static str] {
+        &self.befores
+    }
+
+    pub(super) fn get_afters(&self) -> &[&'static str] {
+        &self.afters
+    }
+
+    //  Every condition is evaluated, see `Condition::and`.
+    pub(super) fn check(&mut self, galaxy: &Galaxy) -> bool {
+        self.conditions
+            .iter_mut()
+            .fold(true, |acc, condition| condition.check(galaxy) & acc)
+    }
+
+    pub(super) fn describe(&self) -> String {
+        if self.labels.is_empty() {
+            self.name.to_owned()
+        } else {
+            format!("{} ({})", self.name, self.labels.join(", "))
+        }
+    }
+}
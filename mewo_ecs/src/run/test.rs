@@ -0,0 +1,864 @@
+use super::fixed::FixedAccumulator;
+use crate::*;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct A(usize);
+impl CheapComponent for A {}
+impl GenericComponent for A {
+    fn mewo_component_duplicate() -> ValueDuplicate {
+        <A as CheapComponent>::mewo_component_duplicate()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct B(usize);
+impl CheapComponent for B {}
+impl GenericComponent for B {
+    fn mewo_component_duplicate() -> ValueDuplicate {
+        <B as CheapComponent>::mewo_component_duplicate()
+    }
+}
+
+//  Conflicting systems must keep their insertion order while others are free to run alongside.
+//  Expected result after one frame:
+//  A: (0 + 1) * 2 = 2
+//  B: (0 + 3)     = 3
+#[test]
+fn test_schedule_parallel() {
+    let mut galaxy = Galaxy::new();
+    let e = galaxy
+        .insert_entity()
+        .insert(A(0))
+        .insert(B(0))
+        .get_entity();
+    galaxy.update();
+
+    fn add_a(q: Query<&mut A>) {
+        for a in q.iter() {
+            a.0 += 1;
+        }
+    }
+    fn double_a(q: Query<&mut A>) {
+        for a in q.iter() {
+            a.0 *= 2;
+        }
+    }
+    fn add_b(q: Query<&mut B>) {
+        for b in q.iter() {
+            b.0 += 3;
+        }
+    }
+
+    let mut schedule = ScheduleBuilder::new()
+        .insert(System::new(add_a))
+        .insert(System::new(add_b))
+        .insert(System::new(double_a))
+        .build()
+        .unwrap();
+    schedule.run(&galaxy);
+    galaxy.update();
+
+    let (a, b) = galaxy
+        .get_entity(e)
+        .unwrap()
+        .get::<(&A, &B)>()
+        .unwrap()
+        .get();
+    assert_eq!(a, &A(2));
+    assert_eq!(b, &B(3));
+}
+
+#[test]
+fn test_schedule_order() {
+    struct Order(Vec<&'static str>);
+    impl Resource for Order {}
+
+    fn push(g: &Galaxy, name: &'static str) {
+        g.get_mut_resource::<Order, _>(std::any::TypeId::of::<Order>())
+            .unwrap()
+            .0
+            .push(name);
+    }
+    fn input(g: &Galaxy) {
+        push(g, "input");
+    }
+    fn physics(g: &Galaxy) {
+        push(g, "physics");
+    }
+    fn render(g: &Galaxy) {
+        push(g, "render");
+    }
+
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Order>(), Order(Vec::new()));
+
+    let mut schedule = ScheduleBuilder::new()
+        .insert(System::new(render).after("physics").resource_mut::<Order>())
+        .insert(
+            System::new(physics)
+                .label("physics")
+                .after("input")
+                .resource_mut::<Order>(),
+        )
+        .insert(System::new(input).label("input").resource_mut::<Order>())
+        .build()
+        .unwrap();
+    schedule.run(&galaxy);
+    assert_eq!(
+        galaxy
+            .get_resource::<Order, _>(std::any::TypeId::of::<Order>())
+            .unwrap()
+            .0,
+        vec!["input", "physics", "render"]
+    );
+
+    let cycle = ScheduleBuilder::new()
+        .insert(System::new(input).label("input").after("render"))
+        .insert(System::new(physics).label("physics").after("input"))
+        .insert(System::new(render).label("render").after("physics"))
+        .build();
+    assert!(matches!(
+        cycle.err().unwrap().error,
+        ErrorType::ScheduleCycle { systems } if systems.len() == 3
+    ));
+
+    let missing = ScheduleBuilder::new()
+        .insert(System::new(input).after("nothing"))
+        .build();
+    assert!(matches!(
+        missing.err().unwrap().error,
+        ErrorType::ScheduleLabelMissing { label: "nothing" }
+    ));
+}
+
+#[test]
+fn test_schedule_conditions() {
+    struct Count(usize);
+    impl Resource for Count {}
+    struct Missing;
+    impl Resource for Missing {}
+
+    fn count(g: &Galaxy) {
+        g.get_mut_resource::<Count, _>(std::any::TypeId::of::<Count>())
+            .unwrap()
+            .0 += 1;
+    }
+
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Count>(), Count(0));
+
+    let missing = || Condition::resource_exists::<Missing, _>(std::any::TypeId::of::<Missing>());
+    let mut schedule = ScheduleBuilder::new()
+        //  Runs on frames 0 and 2.
+        .insert(
+            System::new(count)
+                .run_if(Condition::every_n_frames(2))
+                .resource_mut::<Count>(),
+        )
+        //  Never runs.
+        .insert(
+            System::new(count)
+                .label("needs_missing")
+                .resource_mut::<Count>(),
+        )
+        //  Always runs.
+        .insert(
+            System::new(count)
+                .run_if(!missing().or(missing()))
+                .resource_mut::<Count>(),
+        )
+        .set_run_if("needs_missing", missing())
+        .build()
+        .unwrap();
+    for _ in 0..4 {
+        schedule.run(&galaxy);
+    }
+    assert_eq!(
+        galaxy
+            .get_resource::<Count, _>(std::any::TypeId::of::<Count>())
+            .unwrap()
+            .0,
+        6
+    );
+}
+
+#[test]
+fn test_system_local() {
+    struct Count(usize);
+    impl Resource for Count {}
+
+    fn count(g: &Galaxy, mut calls: Local<usize>) {
+        *calls += 1;
+        g.get_mut_resource::<Count, _>(std::any::TypeId::of::<Count>())
+            .unwrap()
+            .0 += *calls;
+    }
+
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Count>(), Count(0));
+
+    //  Each system keeps its own count: 1 + 2 + 3 = 6.
+    let mut schedule = ScheduleBuilder::new()
+        .insert(System::new(with_local(count)).resource_mut::<Count>())
+        .insert(System::new(with_local(count)).resource_mut::<Count>())
+        .build()
+        .unwrap();
+    for _ in 0..3 {
+        schedule.run(&galaxy);
+    }
+    assert_eq!(
+        galaxy
+            .get_resource::<Count, _>(std::any::TypeId::of::<Count>())
+            .unwrap()
+            .0,
+        12
+    );
+}
+
+#[test]
+fn test_system_params() {
+    struct Step(usize);
+    impl Resource for Step {}
+    struct Total(usize);
+    impl Resource for Total {}
+    struct Missing;
+    impl Resource for Missing {}
+    struct Bump(usize);
+    impl Event for Bump {}
+
+    fn add(q: Query<(&mut A, &B)>, step: Res<Step>, bumps: Events<Bump>) {
+        let bump: usize = bumps.iter().map(|bump| bump.0).sum();
+        for (a, b) in q.iter() {
+            a.0 += b.0 * step.0 + bump;
+        }
+    }
+    fn total(
+        q: Query<&A>,
+        mut total: ResMut<Total>,
+        mut calls: Local<usize>,
+        missing: Option<Res<Missing>>,
+    ) {
+        assert!(missing.is_none());
+        *calls += 1;
+        total.0 = q.iter().map(|a| a.0).sum::<usize>() * *calls;
+    }
+    fn read_b(_q: Query<&B>, _step: Res<Step>) {}
+
+    let mut galaxy = Galaxy::new();
+    galaxy.insert_entity().insert(A(1)).insert(B(2));
+    galaxy.insert_resource(std::any::TypeId::of::<Step>(), Step(3));
+    galaxy.insert_resource(std::any::TypeId::of::<Total>(), Total(0));
+    galaxy.insert_event(Bump(4));
+    galaxy.update();
+
+    //  Accesses come from the parameters.
+    let (add, total, read_b) = (System::new(add), System::new(total), System::new(read_b));
+    assert!(!add.get_access().is_compatible(total.get_access()));
+    assert!(add.get_access().is_compatible(read_b.get_access()));
+    assert!(total.get_access().is_compatible(read_b.get_access()));
+
+    //  `total` only sees `add`'s writes after an update.
+    //  Frame 0: A = 1 + 2 * 3 + 4 = 11, Total = 1 * 1.
+    //  Frame 1: A = 11 + 2 * 3 = 17, Total = 11 * 2.
+    let mut schedule = ScheduleBuilder::new()
+        .insert(add)
+        .insert(total)
+        .insert(read_b)
+        .build()
+        .unwrap();
+    for _ in 0..2 {
+        schedule.run(&galaxy);
+        galaxy.update();
+    }
+    assert_eq!(
+        galaxy
+            .get_resource::<Total, _>(std::any::TypeId::of::<Total>())
+            .unwrap()
+            .0,
+        22
+    );
+}
+
+#[test]
+fn test_system_param_access() {
+    struct Score;
+    impl Resource for Score {}
+
+    fn world(_g: &Galaxy) {}
+    fn world_score(_g: &Galaxy, _score: ResMut<Score>) {}
+    fn read_a(_q: Query<&A>) {}
+    fn write_a(_q: Query<&mut A>) {}
+    fn write_read_a(_w: Query<&mut A>, _r: Query<&A>) {}
+    fn read_read_a(_q: Query<&A>, _p: Query<(&A, &B)>) {}
+    fn score_twice(_r: Res<Score>, _w: ResMut<Score>) {}
+
+    //  `&Galaxy` reads everything, so it only runs alongside readers.
+    let (world, read_a, write_a) = (
+        System::new(world),
+        System::new(read_a),
+        System::new(write_a),
+    );
+    assert!(world.get_access().is_compatible(read_a.get_access()));
+    assert!(!world.get_access().is_compatible(write_a.get_access()));
+    assert!(!write_a.get_access().is_compatible(world.get_access()));
+    assert!(!System::new(world_score)
+        .get_access()
+        .is_compatible(world.get_access()));
+
+    //  Parameters that would wait on each other are caught when building the system.
+    System::new(world_score);
+    System::new(read_read_a);
+    assert!(std::panic::catch_unwind(|| System::new(write_read_a)).is_err());
+    assert!(std::panic::catch_unwind(|| System::new(score_twice)).is_err());
+}
+
+#[test]
+fn test_query_changed_added() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Pos(i32);
+    impl CheapComponent for Pos {}
+    impl GenericComponent for Pos {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Pos as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Hp(i32);
+    impl UniqueComponent for Hp {}
+    impl GenericComponent for Hp {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Hp as UniqueComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Marker;
+    impl CheapComponent for Marker {}
+    impl GenericComponent for Marker {
+        fn mewo_component_duplicate() -> ValueDuplicate {
+            <Marker as CheapComponent>::mewo_component_duplicate()
+        }
+    }
+
+    #[derive(Default)]
+    struct Seen {
+        pos_changed: Vec<Entity>,
+        pos_added: Vec<Entity>,
+        hp_changed: Vec<Entity>,
+    }
+    impl Resource for Seen {}
+
+    fn observe(pos: Query<&Pos>, hp: Query<&Hp>, mut seen: ResMut<Seen>) {
+        seen.pos_changed = pos.changed::<Pos>().eiter().map(|(e, _)| e).collect();
+        seen.pos_added = pos.added::<Pos>().eiter().map(|(e, _)| e).collect();
+        seen.hp_changed = hp.changed::<Hp>().eiter().map(|(e, _)| e).collect();
+        seen.pos_changed.sort_by_key(Entity::id);
+        seen.pos_added.sort_by_key(Entity::id);
+        seen.hp_changed.sort_by_key(Entity::id);
+    }
+
+    fn nudge(pos: Query<&mut Pos>, hp: Query<&mut Hp>) {
+        for pos in pos.with::<Marker>().iter() {
+            pos.0 += 1;
+        }
+        for hp in hp.with::<Marker>().iter() {
+            hp.0 -= 1;
+        }
+    }
+
+    let mut galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Seen>(), Seen::default());
+    let mut observe = System::new(observe);
+    let mut nudge = System::new(nudge);
+    let mut check =
+        |galaxy: &Galaxy, pos_changed: &[Entity], pos_added: &[Entity], hp_changed: &[Entity]| {
+            observe.get_func_mut()(galaxy);
+            let seen = galaxy
+                .get_resource::<Seen, _>(std::any::TypeId::of::<Seen>())
+                .unwrap();
+            assert_eq!(seen.pos_changed, pos_changed);
+            assert_eq!(seen.pos_added, pos_added);
+            assert_eq!(seen.hp_changed, hp_changed);
+        };
+
+    let a = galaxy
+        .insert_entity()
+        .insert(Pos(0))
+        .insert(Hp(10))
+        .insert(Marker)
+        .get_entity();
+    let b = galaxy
+        .insert_entity()
+        .insert(Pos(0))
+        .insert(Hp(10))
+        .get_entity();
+    galaxy.update();
+
+    //  Everything is new to a system that has never run.
+    check(&galaxy, &[a, b], &[a, b], &[a, b]);
+    check(&galaxy, &[], &[], &[]);
+
+    //  Normal components change right away, copycats only after the update.
+    nudge.get_func_mut()(&galaxy);
+    check(&galaxy, &[], &[], &[a]);
+    galaxy.update();
+    check(&galaxy, &[a], &[], &[]);
+    check(&galaxy, &[], &[], &[]);
+
+    //  Moving between storages keeps ticks.
+    galaxy.get_entity(b).unwrap().insert(Marker);
+    let c = galaxy.insert_entity().insert(Pos(0)).get_entity();
+    galaxy.update();
+    check(&galaxy, &[c], &[c], &[]);
+
+    //  Outside of a system, changes since the last update.
+    nudge.get_func_mut()(&galaxy);
+    galaxy.update();
+    let mut changed = galaxy
+        .query::<&Pos>()
+        .changed::<Pos>()
+        .eiter()
+        .map(|(e, _)| e)
+        .collect::<Vec<_>>();
+    changed.sort_by_key(Entity::id);
+    assert_eq!(changed, vec![a, b]);
+    assert_eq!(galaxy.query::<&Pos>().added::<Pos>().iter().count(), 0);
+}
+
+#[test]
+fn test_stages() {
+    struct Seen(Vec<(usize, usize)>);
+    impl Resource for Seen {}
+    struct Ping;
+    impl Event for Ping {}
+
+    fn startup(g: &Galaxy) {
+        g.insert_entity().insert(A(0));
+        g.insert_resource(std::any::TypeId::of::<Seen>(), Seen(Vec::new()));
+    }
+    fn ping(g: &Galaxy) {
+        g.insert_event(Ping);
+    }
+    fn see(g: &Galaxy, q: Query<&A>, pings: Events<Ping>, mut seen: ResMut<Seen>) {
+        seen.0.push((q.iter().count(), pings.len()));
+        if seen.0.len() == 2 {
+            g.set_exit();
+        }
+    }
+
+    let stages = StagesBuilder::new()
+        .insert(Stage::Startup, System::new(startup))
+        .insert(Stage::PreUpdate, System::new(ping))
+        .insert(Stage::Update, System::new(see))
+        .update_after(Stage::PreUpdate)
+        .build()
+        .unwrap();
+
+    //  Startup's entity and PreUpdate's events are both visible to Update.
+    let galaxy = Arc::new(RwLock::new(Galaxy::new()));
+    run_spawn_stages(Arc::clone(&galaxy), stages, |_| {}, |_| {})
+        .join()
+        .unwrap();
+    assert_eq!(
+        galaxy
+            .read()
+            .get_resource::<Seen, _>(std::any::TypeId::of::<Seen>())
+            .unwrap()
+            .0,
+        vec![(1, 1), (1, 1)]
+    );
+}
+
+#[test]
+fn test_stages_exclusive() {
+    struct Counts(Vec<usize>);
+    impl Resource for Counts {}
+
+    fn count(q: Query<&A>, mut counts: ResMut<Counts>) {
+        counts.0.push(q.iter().count());
+    }
+
+    let stages = StagesBuilder::new()
+        .insert_exclusive(Stage::Startup, |g| {
+            g.insert_resource(std::any::TypeId::of::<Counts>(), Counts(Vec::new()));
+        })
+        .insert(Stage::Update, System::new(count))
+        //  Spawns are visible the next frame, after the end of frame update.
+        .insert_exclusive(Stage::Update, |g| {
+            for idx in 0..3 {
+                g.insert_entity().insert(A(idx));
+            }
+            if g.get_resource::<Counts, _>(std::any::TypeId::of::<Counts>())
+                .unwrap()
+                .0
+                .len()
+                == 2
+            {
+                g.set_exit();
+            }
+        })
+        .build()
+        .unwrap();
+
+    let galaxy = Arc::new(RwLock::new(Galaxy::new()));
+    run_spawn_stages(Arc::clone(&galaxy), stages, |_| {}, |_| {})
+        .join()
+        .unwrap();
+    assert_eq!(
+        galaxy
+            .read()
+            .get_resource::<Counts, _>(std::any::TypeId::of::<Counts>())
+            .unwrap()
+            .0,
+        vec![0, 3]
+    );
+}
+
+#[test]
+fn test_exit_shutdown() {
+    struct Order(Vec<&'static str>);
+    impl Resource for Order {}
+
+    fn fail(g: &Galaxy) {
+        g.set_exit_with(Exit::failure(2, "bad"));
+    }
+    //  Only the first exit is kept.
+    fn succeed(g: &Galaxy) {
+        g.set_exit();
+    }
+    fn first(mut order: ResMut<Order>) {
+        order.0.push("first");
+    }
+    fn second(mut order: ResMut<Order>) {
+        order.0.push("second");
+    }
+
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Order>(), Order(Vec::new()));
+    let stages = StagesBuilder::new()
+        .insert(Stage::Update, System::new(fail).label("fail"))
+        .insert(Stage::Update, System::new(succeed).after("fail"))
+        .insert(Stage::Shutdown, System::new(second).label("second"))
+        .insert(Stage::Shutdown, System::new(first).before("second"))
+        .insert_exclusive(Stage::Shutdown, |g| {
+            assert_eq!(
+                g.get_resource::<Order, _>(std::any::TypeId::of::<Order>())
+                    .unwrap()
+                    .0,
+                vec!["first", "second"]
+            );
+            g.remove_resource::<Order, _>(std::any::TypeId::of::<Order>());
+        })
+        .insert_exclusive(Stage::Shutdown, |g| {
+            assert!(!g.has_resource::<Order, _>(std::any::TypeId::of::<Order>()));
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(run_stages(galaxy, stages), Exit::failure(2, "bad"));
+}
+
+#[test]
+fn test_run_control() {
+    #[derive(Default)]
+    struct Frames {
+        live: usize,
+        exclusive: usize,
+        all: usize,
+    }
+    impl Resource for Frames {}
+
+    fn live(mut frames: ResMut<Frames>) {
+        frames.live += 1;
+    }
+    fn drive(g: &Galaxy, control: Res<RunControl>, mut frames: ResMut<Frames>, time: Res<Time>) {
+        frames.all += 1;
+        match frames.all {
+            //  Stepped twice, then paused on the third frame.
+            3 => {
+                assert_eq!(frames.live, 2);
+                assert!(control.is_paused());
+                control.run_until(Condition::new(|g| {
+                    g.get_resource::<Frames, _>(std::any::TypeId::of::<Frames>())
+                        .unwrap()
+                        .live
+                        >= 5
+                }));
+            }
+            8 => {
+                assert_eq!(frames.live, 5);
+                assert_eq!(frames.exclusive, 5);
+                assert_eq!(time.frame(), 5);
+                g.set_exit();
+            }
+            _ => {}
+        }
+    }
+
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Frames>(), Frames::default());
+    let control = RunControl::new();
+    control.step(2);
+    let stages = StagesBuilder::new()
+        .control(control.clone())
+        .insert(Stage::Update, System::new(live).label("live"))
+        .insert(
+            Stage::Update,
+            System::new(drive).after("live").run_while_paused(),
+        )
+        .insert_exclusive(Stage::Update, |g| {
+            g.get_mut_resource::<Frames, _>(std::any::TypeId::of::<Frames>())
+                .unwrap()
+                .exclusive += 1;
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(run_stages(galaxy, stages), Exit::Success);
+    assert!(control.is_paused());
+}
+
+#[test]
+fn test_run_control_paused_updates() {
+    struct Frames(usize);
+    impl Resource for Frames {}
+
+    let control = RunControl::new();
+    control.pause();
+    let stages = StagesBuilder::new()
+        .control(control.clone())
+        .insert_resource(std::any::TypeId::of::<Frames>(), Frames(0))
+        .insert(
+            Stage::Update,
+            System::new(|mut frames: ResMut<Frames>| frames.0 += 1),
+        )
+        .build()
+        .unwrap();
+    let galaxy = Arc::new(RwLock::new(Galaxy::new()));
+    let handle = run_spawn_stages(Arc::clone(&galaxy), stages, |_| {}, |_| {});
+
+    let frames = |galaxy: &Arc<RwLock<Galaxy>>| {
+        galaxy
+            .read()
+            .get_resource::<Frames, _>(std::any::TypeId::of::<Frames>())
+            .map(|frames| frames.0)
+    };
+    let count = |galaxy: &Arc<RwLock<Galaxy>>| galaxy.read().query::<&A>().iter().count();
+
+    //  Nothing runs while paused, so nothing is updated either.
+    //  `Startup` still updates, so wait that out first.
+    std::thread::sleep(RunControl::PAUSED_FRAME * 5);
+    galaxy.read().insert_entity().insert(A(0));
+    std::thread::sleep(RunControl::PAUSED_FRAME * 5);
+    assert_eq!(frames(&galaxy), Some(0));
+    assert_eq!(count(&galaxy), 0);
+
+    control.step(1);
+    std::thread::sleep(RunControl::PAUSED_FRAME * 5);
+    assert_eq!(frames(&galaxy), Some(1));
+    assert_eq!(count(&galaxy), 1);
+
+    galaxy.read().set_exit();
+    assert_eq!(handle.join().unwrap(), Exit::Success);
+    assert_eq!(frames(&galaxy), Some(1));
+}
+
+#[test]
+fn test_plugins() {
+    struct Score(usize);
+    impl Resource for Score {}
+
+    struct ScorePlugin;
+    impl Plugin for ScorePlugin {
+        fn build(&self, stages: StagesBuilder) -> StagesBuilder {
+            stages
+                .insert_resource(std::any::TypeId::of::<Score>(), Score(1))
+                .register_component::<A>()
+        }
+    }
+
+    struct BonusPlugin;
+    impl Plugin for BonusPlugin {
+        fn dependencies() -> Vec<&'static str> {
+            vec![ScorePlugin::name()]
+        }
+
+        fn build(&self, stages: StagesBuilder) -> StagesBuilder {
+            //  `Score` already exists during `Startup`.
+            stages.insert(
+                Stage::Startup,
+                System::new(|mut score: ResMut<Score>, g: &Galaxy| {
+                    score.0 += 1;
+                    g.set_exit();
+                }),
+            )
+        }
+    }
+
+    //  Dependencies can be added in any order.
+    let stages = StagesBuilder::new()
+        .add_plugin(BonusPlugin)
+        .add_plugin(ScorePlugin)
+        .build()
+        .unwrap();
+    let galaxy = Arc::new(RwLock::new(Galaxy::new()));
+    run_spawn_stages(Arc::clone(&galaxy), stages, |_| {}, |_| {})
+        .join()
+        .unwrap();
+    assert_eq!(
+        galaxy
+            .read()
+            .get_resource::<Score, _>(std::any::TypeId::of::<Score>())
+            .unwrap()
+            .0,
+        2
+    );
+
+    let missing = StagesBuilder::new().add_plugin(BonusPlugin).build();
+    assert!(matches!(
+        missing.err().unwrap().error,
+        ErrorType::PluginDependencyMissing { dependency, .. } if dependency == ScorePlugin::name()
+    ));
+
+    let duplicate = StagesBuilder::new()
+        .add_plugin(ScorePlugin)
+        .add_plugin(ScorePlugin)
+        .build();
+    assert!(matches!(
+        duplicate.err().unwrap().error,
+        ErrorType::PluginDuplicate { plugin } if plugin == ScorePlugin::name()
+    ));
+}
+
+#[test]
+fn test_fixed_accumulator() {
+    let mut accumulator =
+        FixedAccumulator::new(FixedTimestep::new(Duration::from_millis(10)).max_steps(3));
+    assert_eq!(accumulator.advance(Duration::from_millis(5)), 0);
+    assert_eq!(accumulator.get_fixed_time().alpha(), 0.5);
+    assert_eq!(accumulator.advance(Duration::from_millis(20)), 2);
+    assert_eq!(accumulator.get_fixed_time().alpha(), 0.5);
+    //  A hitch only catches up by `max_steps` and keeps the remainder.
+    assert_eq!(accumulator.advance(Duration::from_millis(100)), 3);
+    assert_eq!(accumulator.get_fixed_time().alpha(), 0.5);
+}
+
+#[test]
+fn test_fixed_timestep_from_hz() {
+    assert_eq!(
+        FixedTimestep::from_hz(50.0).get_step(),
+        Duration::from_millis(20)
+    );
+    for hz in [0.0, -60.0, f64::NAN, f64::MIN_POSITIVE] {
+        assert!(std::panic::catch_unwind(|| FixedTimestep::from_hz(hz)).is_err());
+    }
+}
+
+//  Fixed steps run between frames, but events sent during a frame must still reach the next one.
+#[test]
+fn test_fixed_events() {
+    struct Ticker(ManualClock);
+    impl Resource for Ticker {}
+    struct Steps(usize);
+    impl Resource for Steps {}
+    struct Seen(Vec<(usize, usize)>);
+    impl Resource for Seen {}
+    struct Ping;
+    impl Event for Ping {}
+
+    fn step(mut steps: ResMut<Steps>) {
+        steps.0 += 1;
+    }
+    fn see(g: &Galaxy, pings: Events<Ping>, steps: Res<Steps>, mut seen: ResMut<Seen>) {
+        seen.0.push((pings.len(), steps.0));
+        if seen.0.len() == 3 {
+            g.set_exit();
+        }
+    }
+    //  Each frame is one fixed step long.
+    fn ping(g: &Galaxy, ticker: Res<Ticker>) {
+        g.insert_event(Ping);
+        ticker.0.advance(Duration::from_millis(10));
+    }
+
+    let clock = ManualClock::new();
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Ticker>(), Ticker(clock.clone()));
+    galaxy.insert_resource(std::any::TypeId::of::<Steps>(), Steps(0));
+    galaxy.insert_resource(std::any::TypeId::of::<Seen>(), Seen(Vec::new()));
+    galaxy.insert_resource(Time::single_resource(), Time::with_clock(clock));
+
+    let fixed = ScheduleBuilder::new()
+        .insert(System::new(step))
+        .build()
+        .unwrap();
+    let stages = StagesBuilder::new()
+        .insert(Stage::Update, System::new(see))
+        .insert(Stage::Last, System::new(ping))
+        .build()
+        .unwrap();
+
+    let galaxy = Arc::new(RwLock::new(galaxy));
+    run_spawn_fixed(
+        Arc::clone(&galaxy),
+        fixed,
+        stages,
+        FixedTimestep::new(Duration::from_millis(10)),
+        |_| {},
+        |_| {},
+    )
+    .join()
+    .unwrap();
+    assert_eq!(
+        galaxy
+            .read()
+            .get_resource::<Seen, _>(std::any::TypeId::of::<Seen>())
+            .unwrap()
+            .0,
+        vec![(0, 0), (1, 1), (1, 2)]
+    );
+}
+
+#[test]
+fn test_time_manual_clock() {
+    struct Ticker(ManualClock);
+    impl Resource for Ticker {}
+
+    fn tick(g: &Galaxy) {
+        g.get_resource::<Ticker, _>(std::any::TypeId::of::<Ticker>())
+            .unwrap()
+            .0
+            .advance(Duration::from_millis(10));
+        if g.get_resource::<Time, _>(Time::single_resource())
+            .unwrap()
+            .frame()
+            == 3
+        {
+            g.set_exit();
+        }
+    }
+
+    let clock = ManualClock::new();
+    let galaxy = Galaxy::new();
+    galaxy.insert_resource(std::any::TypeId::of::<Ticker>(), Ticker(clock.clone()));
+    let mut time = Time::with_clock(clock);
+    time.set_scale(0.5);
+    galaxy.insert_resource(Time::single_resource(), time);
+
+    let galaxy = Arc::new(RwLock::new(galaxy));
+    run_spawn_locked(Arc::clone(&galaxy), vec![Box::new(tick)], |_| {}, |_| {})
+        .join()
+        .unwrap();
+
+    let galaxy = galaxy.read();
+    let time = galaxy
+        .get_resource::<Time, _>(Time::single_resource())
+        .unwrap();
+    assert_eq!(time.frame(), 3);
+    assert_eq!(time.real_delta(), Duration::from_millis(10));
+    assert_eq!(time.delta(), Duration::from_millis(5));
+    assert_eq!(time.real_elapsed(), Duration::from_millis(30));
+    assert_eq!(time.elapsed(), Duration::from_millis(15));
+}
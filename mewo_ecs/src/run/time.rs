@@ -0,0 +1,207 @@
+use super::*;
+use parking_lot::Mutex;
+use std::{
+    any::TypeId,
+    time::{Duration, Instant},
+};
+
+/// A source of time for [`Time`].
+/// `now` only needs to increase relative to some fixed point.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Duration;
+}
+
+/// The default [`Clock`], which follows real time.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A [`Clock`] that only moves when told to.
+/// Clones share the same time, so keep one around to drive [`Time`] deterministically.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock {
+            now: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock() += by;
+    }
+
+    pub fn set(&self, now: Duration) {
+        *self.now.lock() = now;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock()
+    }
+}
+
+/// Frame timing maintained by the runners after each [`Galaxy::update`].
+/// If no `Time` exists when a runner starts, one using [`SystemClock`] is inserted.
+/// Insert your own with [`Time::with_clock`] beforehand to control the clock.
+///
+/// Scaled time (`delta`, `elapsed`) follows `scale` and stops while paused.
+/// Real time (`real_delta`, `real_elapsed`) always follows the clock.
+pub struct Time {
+    clock: Box<dyn Clock>,
+    last: Duration,
+    delta: Duration,
+    real_delta: Duration,
+    elapsed: Duration,
+    real_elapsed: Duration,
+    frame: u64,
+    scale: f64,
+    paused: bool,
+}
+
+impl Resource for Time {}
+
+impl Time {
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock::new())
+    }
+
+    pub fn with_clock<C: Clock + 'static>(clock: C) -> Self {
+        Time {
+            last: clock.now(),
+            clock: Box::new(clock),
+            delta: Duration::ZERO,
+            real_delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            real_elapsed: Duration::ZERO,
+            frame: 0,
+            scale: 1.0,
+            paused: false,
+        }
+    }
+
+    pub fn single_resource() -> TypeId {
+        TypeId::of::<Time>()
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn real_delta(&self) -> Duration {
+        self.real_delta
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn real_elapsed(&self) -> Duration {
+        self.real_elapsed
+    }
+
+    /// The number of updates since the runner started.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.0);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    fn tick(&mut self) {
+        self.tick_real();
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            self.real_delta.mul_f64(self.scale)
+        };
+        self.elapsed += self.delta;
+        self.frame += 1;
+    }
+
+    //  For frames paused by a `RunControl`, which don't count as frames at all.
+    fn tick_frozen(&mut self) {
+        self.tick_real();
+        self.delta = Duration::ZERO;
+    }
+
+    fn tick_real(&mut self) {
+        let now = self.clock.now();
+        self.real_delta = now.saturating_sub(self.last);
+        self.last = now;
+        self.real_elapsed += self.real_delta;
+    }
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(super) fn time_maybe_insert(galaxy: &Galaxy) {
+    if !galaxy.has_resource::<Time, _>(Time::single_resource()) {
+        galaxy.insert_resource(Time::single_resource(), Time::new());
+    }
+}
+
+//  Call right after `Galaxy::update`.
+pub(super) fn time_tick(galaxy: &Galaxy) {
+    if let Some(mut time) = galaxy.get_mut_resource::<Time, _>(Time::single_resource()) {
+        time.tick();
+    }
+}
+
+pub(super) fn time_tick_frozen(galaxy: &Galaxy) {
+    if let Some(mut time) = galaxy.get_mut_resource::<Time, _>(Time::single_resource()) {
+        time.tick_frozen();
+    }
+}
+
+pub(super) fn time_delta(galaxy: &Galaxy) -> Duration {
+    galaxy
+        .get_resource::<Time, _>(Time::single_resource())
+        .map(|time| time.delta())
+        .unwrap_or_default()
+}
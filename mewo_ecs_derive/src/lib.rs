@@ -1,9 +1,60 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
-#[proc_macro_derive(CheapComponent)]
+//  `#[mewo(storage = "direct")]` or `#[mewo(storage = "double_buffered")]`.
+fn component_storage(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Ident>> {
+    let mut storage = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("mewo")) {
+        let list = match attr.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `#[mewo(...)]`")),
+        };
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if path.is_ident("storage") => {
+                    storage = Some(match lit.value().as_str() {
+                        "direct" => format_ident!("Direct"),
+                        "double_buffered" => format_ident!("DoubleBuffered"),
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                lit.clone(),
+                                format!(
+                                    "unknown storage `{}`, expected `direct` or `double_buffered`",
+                                    other
+                                ),
+                            ))
+                        }
+                    });
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "expected `storage = \"...\"`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(storage)
+}
+
+#[proc_macro_derive(CheapComponent, attributes(mewo))]
 pub fn cheap_component_macro_derive(input: TokenStream) -> TokenStream {
     let mut ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let storage = match component_storage(&ast.attrs) {
+        Ok(storage) => storage.map(|storage| {
+            quote! {
+                fn mewo_component_storage() -> ComponentStorage {
+                    ComponentStorage::#storage
+                }
+            }
+        }),
+        Err(e) => return e.to_compile_error().into(),
+    };
     let name = &ast.ident;
     let generics = &mut ast.generics;
     for param in &mut generics.params {
@@ -19,6 +70,7 @@ pub fn cheap_component_macro_derive(input: TokenStream) -> TokenStream {
             fn mewo_component_duplicate() -> ValueDuplicate {
                 <#name as CheapComponent>::mewo_component_duplicate()
             }
+            #storage
         }
     };
     gen.into()
@@ -104,3 +156,32 @@ pub fn single_resource_macro_derive(input: TokenStream) -> TokenStream {
     };
     gen.into()
 }
+
+#[test]
+fn test_component_storage() {
+    let ast: syn::DeriveInput = syn::parse_quote! {
+        #[mewo(storage = "direct")]
+        struct A;
+    };
+    assert_eq!(component_storage(&ast.attrs).unwrap().unwrap(), "Direct");
+
+    let ast: syn::DeriveInput = syn::parse_quote! {
+        struct A;
+    };
+    assert!(component_storage(&ast.attrs).unwrap().is_none());
+
+    let ast: syn::DeriveInput = syn::parse_quote! {
+        #[mewo(storage = "triple_buffered")]
+        struct A;
+    };
+    assert_eq!(
+        component_storage(&ast.attrs).unwrap_err().to_string(),
+        "unknown storage `triple_buffered`, expected `direct` or `double_buffered`"
+    );
+
+    let ast: syn::DeriveInput = syn::parse_quote! {
+        #[mewo(storage)]
+        struct A;
+    };
+    assert!(component_storage(&ast.attrs).is_err());
+}